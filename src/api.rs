@@ -1,53 +1,118 @@
 //! Works with the API
 
 use crate::{
-    error::{APIError, Result},
+    error::{APIError, CaptchaChallenge, Error, ErrorCode, Result},
     API_VERSION,
 };
-use reqwest::{Client, Response};
+use reqwest::{Client, Method, RequestBuilder, Response, Url};
 use serde::de::DeserializeOwned;
 use serde_json::{from_value, Map, Value};
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-#[cfg(feature = "trace_response")]
 mod trace {
+    #[cfg(feature = "trace_response")]
     use chrono::Local;
     use std::fs::write;
     use std::path::Path;
+    #[cfg(feature = "trace_response")]
+    use std::path::PathBuf;
 
-    pub fn try_trace_failed_response(response: &str, error_message: &str) {
-        try_trace_response("/failed", response, error_message);
+    /// Where and what to trace; the unset options fall back to
+    /// the `RVK_TRACE_DIR` and `RVK_TRACE_ALL` environment variables.
+    #[cfg(feature = "trace_response")]
+    #[derive(Debug, Clone)]
+    pub struct Settings {
+        pub enabled: bool,
+        pub dir: Option<PathBuf>,
+        pub all: Option<bool>,
     }
 
-    pub fn try_trace_succeeded_response(response: &str) {
-        try_trace_response("/succeeded", response, "");
+    #[cfg(feature = "trace_response")]
+    impl Default for Settings {
+        fn default() -> Self {
+            Self {
+                enabled: true,
+                dir: None,
+                all: None,
+            }
+        }
     }
 
-    fn try_trace_response(subdir: &str, response: &str, error_message: &str) {
-        let mut dir = std::env::var("RVK_TRACE_DIR").unwrap_or_else(|_| {
-            let mut local = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-            local.push_str("/.cache/rvk");
-            local
-        });
-        dir.push_str(subdir);
-        if std::fs::create_dir_all(Path::new(&dir)).is_err() {
-            log::error!("failed to create directory to trace response {}", dir);
+    #[cfg(feature = "trace_response")]
+    impl Settings {
+        pub fn try_trace_failed_response(&self, response: &str, error_message: &str) {
+            if self.enabled {
+                try_trace_response(&self.dir().join("failed"), &now(), response, error_message);
+            }
+        }
+
+        pub fn try_trace_succeeded_response(&self, response: &str) {
+            if self.enabled && self.all() {
+                try_trace_response(&self.dir().join("succeeded"), &now(), response, "");
+            }
+        }
+
+        fn dir(&self) -> PathBuf {
+            self.dir.clone().unwrap_or_else(|| {
+                std::env::var("RVK_TRACE_DIR")
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|_| {
+                        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+                        Path::new(&home).join(".cache/rvk")
+                    })
+            })
+        }
+
+        fn all(&self) -> bool {
+            self.all
+                .unwrap_or_else(|| std::env::var("RVK_TRACE_ALL").as_deref() == Ok("1"))
+        }
+    }
+
+    /// The time to name the trace files with.
+    #[cfg(feature = "trace_response")]
+    pub fn now() -> String {
+        format!("{}", Local::now().format("%Y-%m-%d_%H-%M-%S"))
+    }
+
+    /// The time to name the trace files with (in milliseconds since the epoch without `chrono`).
+    #[cfg(not(feature = "trace_response"))]
+    pub fn now() -> String {
+        let since_epoch = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        since_epoch.as_millis().to_string()
+    }
+
+    /// Writes the `response` into `<dir>/<name>.json`
+    /// and the `error_message` (if not empty) into `<dir>/<name>_msg.txt`.
+    pub fn try_trace_response(dir: &Path, name: &str, response: &str, error_message: &str) {
+        if std::fs::create_dir_all(dir).is_err() {
+            log::error!(
+                "failed to create directory to trace response {}",
+                dir.display()
+            );
             return;
         }
 
-        dir.push_str(format!("{}", Local::now().format("/%Y-%m-%d_%H-%M-%S")).as_str());
-        let json = dir.clone() + ".json";
-        if write(&Path::new(&json), response.as_bytes()).is_err() {
-            log::error!("failed to write file {}", json);
+        let json = dir.join(name.to_string() + ".json");
+        if write(&json, response.as_bytes()).is_err() {
+            log::error!("failed to write file {}", json.display());
         } else {
-            log::debug!("write response into file {}", json);
+            log::debug!("write response into file {}", json.display());
         }
         if !error_message.is_empty() {
-            let msg = dir + "_msg.txt";
-            if write(&Path::new(&msg), error_message.as_bytes()).is_err() {
-                log::error!("failed to write file {}", msg);
+            let msg = dir.join(name.to_string() + "_msg.txt");
+            if write(&msg, error_message.as_bytes()).is_err() {
+                log::error!("failed to write file {}", msg.display());
             } else {
-                log::debug!("write problem message into file {}", msg);
+                log::debug!("write problem message into file {}", msg.display());
             }
         }
     }
@@ -56,11 +121,277 @@ mod trace {
 /// A HashMap which contains method parameters
 pub type Params = HashMap<String, String>;
 
+/// Inserting the typed values into [`Params`](type.Params.html).
+pub trait ParamsExt {
+    /// Inserts a number (or any other `Display` value).
+    fn insert_num(&mut self, key: impl Into<String>, n: impl Display) -> Option<String>;
+
+    /// Inserts a boolean as `1` or `0`, as the API expects (not as `true` or `false`).
+    fn insert_bool(&mut self, key: impl Into<String>, b: bool) -> Option<String>;
+}
+
+impl ParamsExt for Params {
+    fn insert_num(&mut self, key: impl Into<String>, n: impl Display) -> Option<String> {
+        self.insert(key.into(), n.to_string())
+    }
+
+    fn insert_bool(&mut self, key: impl Into<String>, b: bool) -> Option<String> {
+        self.insert(key.into(), if b { "1" } else { "0" }.into())
+    }
+}
+
+/// The URL the method names are appended to by default.
+pub const DEFAULT_BASE_URL: &str = "https://api.vk.com/method/";
+
+/// The longest URL of a GET request; the calls with longer ones are sent as POST with a form body.
+pub const MAX_GET_URL_LEN: usize = 2048;
+
+/// The delay before retrying a call failed with "Too many requests per second" (error 6)
+/// which fits the limit of 3 requests per second, e.g. for `Backoff::Fixed`.
+pub const RETRY_DELAY: Duration = Duration::from_millis(350);
+
+/// The delays between the retries of a call.
+pub enum Backoff {
+    /// The same delay before each retry.
+    Fixed(Duration),
+    /// The `base` delay before the first retry, multiplied by `factor` before each next one,
+    /// up to `max`.
+    Exponential {
+        base: Duration,
+        factor: f64,
+        max: Duration,
+    },
+    /// The delay computed from the number of the retry (starting with 0).
+    Custom(Box<dyn Fn(u32) -> Duration + Send + Sync>),
+}
+
+impl Backoff {
+    /// The delay before the retry number `retry` (starting with 0).
+    ///
+    /// ```
+    /// # use rvk::api::Backoff;
+    /// # use std::time::Duration;
+    /// let backoff = Backoff::Exponential {
+    ///     base: Duration::from_millis(100),
+    ///     factor: 2.0,
+    ///     max: Duration::from_secs(1),
+    /// };
+    /// assert_eq!(backoff.delay(2), Duration::from_millis(400));
+    /// assert_eq!(backoff.delay(10), Duration::from_secs(1));
+    /// ```
+    pub fn delay(&self, retry: u32) -> Duration {
+        match self {
+            Backoff::Fixed(delay) => *delay,
+            Backoff::Exponential { base, factor, max } => {
+                let nanos = base.as_nanos() as f64 * factor.powf(f64::from(retry));
+                if nanos.is_finite() && nanos < max.as_nanos() as f64 {
+                    // rounded as 0.1 * 3.0 is not exactly 0.3
+                    Duration::from_nanos(nanos.max(0.0).round() as u64)
+                } else {
+                    *max
+                }
+            }
+            Backoff::Custom(delay) => delay(retry),
+        }
+    }
+}
+
+impl std::fmt::Debug for Backoff {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Backoff::Fixed(delay) => f.debug_tuple("Fixed").field(delay).finish(),
+            Backoff::Exponential { base, factor, max } => f
+                .debug_struct("Exponential")
+                .field("base", base)
+                .field("factor", factor)
+                .field("max", max)
+                .finish(),
+            Backoff::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
 /// An API client used to call API methods.
-#[derive(Debug)]
+#[derive(Clone)]
 pub struct APIClient {
     client: Client,
     token: String,
+    secret: Option<String>,
+    base_url: String,
+    timeout: Option<Duration>,
+    retries: u32,
+    backoff: Arc<Backoff>,
+    deadline: Option<Duration>,
+    #[cfg(feature = "trace_response")]
+    trace: trace::Settings,
+}
+
+/// Written by the `Debug` impls in place of the access token and the secret.
+const REDACTED: &str = "<redacted>";
+
+impl std::fmt::Debug for APIClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut s = f.debug_struct("APIClient");
+        s.field("client", &self.client)
+            .field("token", &REDACTED)
+            .field("secret", &self.secret.as_ref().map(|_| REDACTED))
+            .field("base_url", &self.base_url)
+            .field("timeout", &self.timeout)
+            .field("retries", &self.retries)
+            .field("backoff", &self.backoff)
+            .field("deadline", &self.deadline);
+        #[cfg(feature = "trace_response")]
+        s.field("trace", &self.trace);
+        s.finish()
+    }
+}
+
+/// The response of [`APIClient::call_method_detailed`](struct.APIClient.html#method.call_method_detailed)
+/// along with the details of the call.
+#[derive(Debug, Clone)]
+pub struct DetailedResponse<T> {
+    pub response: T,
+    /// `GET`, or `POST` if the URL would be longer than [`MAX_GET_URL_LEN`](constant.MAX_GET_URL_LEN.html)
+    pub http_method: Method,
+}
+
+/// A builder for an [`APIClient`](struct.APIClient.html) with non-default settings.
+pub struct APIClientBuilder {
+    token: String,
+    secret: Option<String>,
+    base_url: String,
+    timeout: Option<Duration>,
+    proxy: Option<reqwest::Proxy>,
+    min_tls_version: Option<reqwest::tls::Version>,
+    retries: u32,
+    backoff: Arc<Backoff>,
+    deadline: Option<Duration>,
+    #[cfg(feature = "trace_response")]
+    trace: trace::Settings,
+}
+
+impl std::fmt::Debug for APIClientBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut s = f.debug_struct("APIClientBuilder");
+        s.field("token", &REDACTED)
+            .field("secret", &self.secret.as_ref().map(|_| REDACTED))
+            .field("base_url", &self.base_url)
+            .field("timeout", &self.timeout)
+            .field("proxy", &self.proxy)
+            .field("min_tls_version", &self.min_tls_version)
+            .field("retries", &self.retries)
+            .field("backoff", &self.backoff)
+            .field("deadline", &self.deadline);
+        #[cfg(feature = "trace_response")]
+        s.field("trace", &self.trace);
+        s.finish()
+    }
+}
+
+impl APIClientBuilder {
+    /// Sets the URL the method names are appended to,
+    /// [`DEFAULT_BASE_URL`](constant.DEFAULT_BASE_URL.html) by default.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Sets the application secret.
+    ///
+    /// When set, every request is signed with the `sig` parameter, as required by
+    /// the [server methods](https://vk.com/dev/secure).
+    pub fn secret(mut self, secret: impl Into<String>) -> Self {
+        self.secret = Some(secret.into());
+        self
+    }
+
+    /// Sets the timeout of a single request (no timeout by default).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the proxy to send all the requests through.
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Sets the minimum TLS version the client accepts (VK supports TLS 1.2 and newer).
+    ///
+    /// Relies on the TLS backend of `reqwest` (its `default-tls` feature, enabled by rvk).
+    pub fn min_tls_version(mut self, version: reqwest::tls::Version) -> Self {
+        self.min_tls_version = Some(version);
+        self
+    }
+
+    /// Sets how many times a call failed with "Too many requests per second" (error 6)
+    /// is retried and the delays between the retries (not retried by default).
+    pub fn retry(mut self, retries: u32, backoff: Backoff) -> Self {
+        self.retries = retries;
+        self.backoff = Arc::new(backoff);
+        self
+    }
+
+    /// Sets the time a call may take in total, including all the retries and the delays between them.
+    ///
+    /// A call which would cross it fails with [`Error::DeadlineExceeded`](../error/enum.Error.html#variant.DeadlineExceeded).
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Enables or disables tracing the responses (enabled by default).
+    #[cfg(feature = "trace_response")]
+    pub fn trace(mut self, enabled: bool) -> Self {
+        self.trace.enabled = enabled;
+        self
+    }
+
+    /// Sets the directory to trace the responses into, overriding `RVK_TRACE_DIR`.
+    #[cfg(feature = "trace_response")]
+    pub fn trace_dir(mut self, dir: impl Into<std::path::PathBuf>) -> Self {
+        self.trace.dir = Some(dir.into());
+        self
+    }
+
+    /// Sets whether the succeeded responses are traced too, overriding `RVK_TRACE_ALL`.
+    #[cfg(feature = "trace_response")]
+    pub fn trace_all(mut self, all: bool) -> Self {
+        self.trace.all = Some(all);
+        self
+    }
+
+    /// Creates the `APIClient`.
+    ///
+    /// # Panics
+    /// This method panics if native TLS backend cannot be created or initialized by the `reqwest` crate.
+    ///
+    /// See [reqwest docs](https://docs.rs/reqwest/0.10/reqwest/struct.Client.html#panic) for more information.
+    pub fn build(self) -> APIClient {
+        let mut client = Client::builder();
+        if let Some(timeout) = self.timeout {
+            client = client.timeout(timeout);
+        }
+        if let Some(proxy) = self.proxy {
+            client = client.proxy(proxy);
+        }
+        if let Some(version) = self.min_tls_version {
+            client = client.min_tls_version(version);
+        }
+        APIClient {
+            client: client.build().expect("failed to create the HTTP client"),
+            token: self.token,
+            secret: self.secret,
+            base_url: self.base_url,
+            timeout: self.timeout,
+            retries: self.retries,
+            backoff: self.backoff,
+            deadline: self.deadline,
+            #[cfg(feature = "trace_response")]
+            trace: self.trace,
+        }
+    }
 }
 
 impl APIClient {
@@ -71,63 +402,776 @@ impl APIClient {
     ///
     /// See [reqwest docs](https://docs.rs/reqwest/0.10/reqwest/struct.Client.html#panic) for more information.
     pub fn new(token: impl Into<String>) -> APIClient {
+        APIClient::builder(token).build()
+    }
+
+    /// Creates an `APIClientBuilder`, given an access token.
+    pub fn builder(token: impl Into<String>) -> APIClientBuilder {
+        APIClientBuilder {
+            token: token.into(),
+            secret: None,
+            base_url: DEFAULT_BASE_URL.into(),
+            timeout: None,
+            proxy: None,
+            min_tls_version: None,
+            retries: 0,
+            backoff: Arc::new(Backoff::Fixed(RETRY_DELAY)),
+            deadline: None,
+            #[cfg(feature = "trace_response")]
+            trace: Default::default(),
+        }
+    }
+
+    /// Creates an `APIClient` with another access `token`, but the same settings.
+    ///
+    /// The underlying HTTP client (and so its connection pool) is shared with this one.
+    pub fn clone_with_token(&self, token: impl Into<String>) -> APIClient {
         APIClient {
-            client: Client::new(),
             token: token.into(),
+            ..self.clone()
         }
     }
 
+    /// Downloads the image of the `captcha`, returning its bytes and content type (if known).
+    pub async fn captcha_image(
+        &self,
+        captcha: &CaptchaChallenge,
+    ) -> Result<(Vec<u8>, Option<String>)> {
+        let response = self.fetch(&captcha.img).await?;
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(ToOwned::to_owned);
+        let bytes = response.bytes().await?;
+        Ok((bytes.to_vec(), content_type))
+    }
+
+    /// Requests an arbitrary `url` (e.g. of a media file) with the underlying HTTP client,
+    /// failing on the error HTTP statuses.
+    pub(crate) async fn fetch(&self, url: &str) -> Result<Response> {
+        let response = self.client.get(url).send().await?;
+        Ok(response.error_for_status()?)
+    }
+
     /// Calls an API method, given its name and parameters.
     pub async fn call_method<T: DeserializeOwned>(
         &self,
         method_name: &str,
-        mut params: Params,
+        params: Params,
     ) -> Result<T> {
-        params.insert("v".into(), API_VERSION.into());
-        params.insert("access_token".into(), self.token.clone());
+        self.call_method_with_version(method_name, params, API_VERSION)
+            .await
+    }
 
-        let response_result: Result<Response> = self
-            .client
-            .get(&("https://api.vk.com/method/".to_owned() + method_name))
-            .query(&params)
-            .send()
+    /// Calls an API method, given its name and parameters, using the API `version`
+    /// instead of [`API_VERSION`](../constant.API_VERSION.html).
+    pub async fn call_method_with_version<T: DeserializeOwned>(
+        &self,
+        method_name: &str,
+        params: Params,
+        version: &str,
+    ) -> Result<T> {
+        self.call(method_name, params, version, |builder| builder)
             .await
-            .map_err(|e| e.into());
-        let response = response_result?;
+            .map(|detailed| detailed.response)
+    }
+
+    /// Calls an API method, given its name and parameters,
+    /// returning the response along with the details of how it was requested.
+    pub async fn call_method_detailed<T: DeserializeOwned>(
+        &self,
+        method_name: &str,
+        params: Params,
+    ) -> Result<DetailedResponse<T>> {
+        self.call(method_name, params, API_VERSION, |builder| builder)
+            .await
+    }
+
+    /// Calls an API method, given its name and parameters,
+    /// letting `customize` adjust the request (e.g. set a timeout or a header) before it is sent.
+    ///
+    /// `customize` must keep the query set up by the client: the `access_token`, `v`, and so on.
+    pub async fn call_method_with<T, F>(
+        &self,
+        method_name: &str,
+        params: Params,
+        customize: F,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned,
+        F: FnOnce(RequestBuilder) -> RequestBuilder,
+    {
+        self.call(method_name, params, API_VERSION, customize)
+            .await
+            .map(|detailed| detailed.response)
+    }
+
+    /// Calls an API method once more after it failed with a captcha
+    /// (see [`APIError::as_captcha`](../error/struct.APIError.html#method.as_captcha)),
+    /// sending the captcha `sid` and the `key` the user has entered.
+    pub async fn call_method_with_captcha<T: DeserializeOwned>(
+        &self,
+        method_name: &str,
+        mut params: Params,
+        sid: &str,
+        key: &str,
+    ) -> Result<T> {
+        params.insert("captcha_sid".into(), sid.into());
+        params.insert("captcha_key".into(), key.into());
+        self.call_method(method_name, params).await
+    }
+
+    /// Calls the method `method_name`, always tracing the response into the `dir`
+    /// (regardless of the `trace_response` feature and the environment):
+    /// the JSON goes into `<time>_<method_name>.json` and the error, if any, into `<time>_<method_name>_msg.txt`.
+    ///
+    /// Meant for debugging a single call.
+    pub async fn call_method_traced<T: DeserializeOwned>(
+        &self,
+        method_name: &str,
+        params: Params,
+        dir: &Path,
+    ) -> Result<T> {
+        let (value, _) = self
+            .request(method_name, params, API_VERSION, |builder| builder)
+            .await?;
+        let response_copy = value.to_string();
+        let res = parse_envelope_raw(value).and_then(|response| decode::<T>(method_name, response));
+        let error_message = match res.as_ref() {
+            Err(e) => e.to_string(),
+            Ok(_) => String::new(),
+        };
+        let name = format!("{}_{}", trace::now(), method_name);
+        trace::try_trace_response(dir, &name, &response_copy, &error_message);
+        res
+    }
 
-        let value_result: Result<Value> = response.json().await.map_err(|e| e.into());
-        let mut value = value_result?;
+    async fn call<T, F>(
+        &self,
+        method_name: &str,
+        params: Params,
+        version: &str,
+        customize: F,
+    ) -> Result<DetailedResponse<T>>
+    where
+        T: DeserializeOwned,
+        F: FnOnce(RequestBuilder) -> RequestBuilder,
+    {
+        let (value, http_method) = self
+            .request(method_name, params, version, customize)
+            .await?;
 
         #[cfg(feature = "trace_response")]
         let response_copy = value.to_string();
 
-        let api_response_result: Result<&mut Map<String, Value>> = value
-            .as_object_mut()
-            .ok_or_else(|| "API response is not an object!".into());
-        let api_response = api_response_result?;
-
-        match api_response.remove("response") {
-            Some(ok) => {
-                let res = from_value::<T>(ok);
-                #[cfg(feature = "trace_response")]
-                if let Err(e) = res.as_ref() {
-                    trace::try_trace_failed_response(
-                        response_copy.as_str(),
-                        format!("{}", e).as_str(),
-                    );
-                } else {
-                    if let Ok(var) = std::env::var("RVK_TRACE_ALL") {
-                        if var == "1" {
-                            trace::try_trace_succeeded_response(response_copy.as_str());
-                        }
-                    }
+        let res = parse_envelope_raw(value).and_then(|response| decode::<T>(method_name, response));
+        #[cfg(feature = "trace_response")]
+        match res.as_ref() {
+            Err(e @ Error::Serde(_)) | Err(e @ Error::Decode { .. }) => {
+                self.trace
+                    .try_trace_failed_response(response_copy.as_str(), format!("{}", e).as_str());
+            }
+            Ok(_) => self
+                .trace
+                .try_trace_succeeded_response(response_copy.as_str()),
+            Err(_) => {}
+        }
+        res.map(|response| DetailedResponse {
+            response,
+            http_method,
+        })
+    }
+
+    /// Sends the request and returns the JSON of the response as is,
+    /// along with the HTTP method it was sent with.
+    async fn request<F>(
+        &self,
+        method_name: &str,
+        mut params: Params,
+        version: &str,
+        customize: F,
+    ) -> Result<(Value, Method)>
+    where
+        F: FnOnce(RequestBuilder) -> RequestBuilder,
+    {
+        params.insert("v".into(), version.into());
+        params.insert("access_token".into(), self.token.clone());
+
+        // the parameters are sent in the same (sorted) order they are signed in
+        let mut query: BTreeMap<String, String> = params.into_iter().collect();
+        if let Some(secret) = &self.secret {
+            let sig = sign(method_name, &query, secret);
+            query.insert("sig".into(), sig);
+        }
+
+        let url = self.base_url.clone() + method_name;
+        let get_url =
+            Url::parse_with_params(&url, &query).map_err(|e| Error::Other(e.to_string()))?;
+        let (http_method, builder) = if get_url.as_str().len() > MAX_GET_URL_LEN {
+            (Method::POST, self.client.post(&url).form(&query))
+        } else {
+            (Method::GET, self.client.get(get_url))
+        };
+        let mut builder = Some(customize(builder));
+
+        let started = Instant::now();
+        let mut retries = self.retries;
+        let mut retry = 0;
+        loop {
+            // a request with a streaming body can not be retried
+            let attempt = match builder.as_ref().and_then(RequestBuilder::try_clone) {
+                Some(attempt) => attempt,
+                None => {
+                    retries = 0;
+                    builder.take().expect("the request is not sent yet")
                 }
-                Ok(res?)
+            };
+            let value = self.attempt(attempt, started).await?;
+            let too_many_requests = value["error"]["error_code"].as_u64().map(ErrorCode::from)
+                == Some(ErrorCode::TooManyRequests);
+            if retries == 0 || !too_many_requests {
+                return Ok((value, http_method));
             }
-            None => match api_response.remove("error") {
-                Some(err) => Err(from_value::<APIError>(err)?.into()),
-                None => Err("The API responded with neither a response nor an error!".into()),
-            },
+            let delay = self.backoff.delay(retry);
+            if let Some(deadline) = self.deadline {
+                if started.elapsed() + delay >= deadline {
+                    return Err(Error::DeadlineExceeded(deadline));
+                }
+            }
+            retries -= 1;
+            retry += 1;
+            log::debug!("retrying {} in {:?}", method_name, delay);
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Sends a single request of a call `started` at the time,
+    /// within its timeout but no longer than the time remaining until the deadline.
+    async fn attempt(&self, mut builder: RequestBuilder, started: Instant) -> Result<Value> {
+        if let Some(deadline) = self.deadline {
+            let remaining = deadline
+                .checked_sub(started.elapsed())
+                .ok_or(Error::DeadlineExceeded(deadline))?;
+            // the timeout of the request (e.g. set by `call_method_with`) overrides the one of the client
+            let (client, request) = builder.build_split();
+            let mut request = request?;
+            let timeout = request.timeout().copied().or(self.timeout);
+            *request.timeout_mut() =
+                Some(timeout.map_or(remaining, |timeout| timeout.min(remaining)));
+            builder = RequestBuilder::from_parts(client, request);
+        }
+        let deadline_exceeded = |e: reqwest::Error| match self.deadline {
+            Some(deadline) if e.is_timeout() && started.elapsed() >= deadline => {
+                Error::DeadlineExceeded(deadline)
+            }
+            _ => e.into(),
+        };
+        let response = builder.send().await.map_err(deadline_exceeded)?;
+        response.json().await.map_err(deadline_exceeded)
+    }
+}
+
+/// Parses the API response envelope (`{"response": ...}` or `{"error": ...}`),
+/// deserializing the `response` as `T`.
+pub fn parse_envelope<T: DeserializeOwned>(value: Value) -> Result<T> {
+    let response = parse_envelope_raw(value)?;
+    Ok(from_response::<T>(&response)?)
+}
+
+/// Parses the API response envelope (`{"response": ...}` or `{"error": ...}`),
+/// returning the `response` as is.
+pub fn parse_envelope_raw(mut value: Value) -> Result<Value> {
+    let api_response_result: Result<&mut Map<String, Value>> = value
+        .as_object_mut()
+        .ok_or_else(|| "API response is not an object!".into());
+    let api_response = api_response_result?;
+
+    // both should never be present, but if they are, the error wins
+    match (
+        api_response.remove("response"),
+        api_response.remove("error"),
+    ) {
+        (Some(_), Some(err)) => {
+            log::warn!("the API responded with both a response and an error, using the error");
+            Err(from_value::<APIError>(err)?.into())
+        }
+        (Some(ok), None) => Ok(ok),
+        (None, Some(err)) => Err(from_value::<APIError>(err)?.into()),
+        (None, None) => Err("The API responded with neither a response nor an error!".into()),
+    }
+}
+
+/// Deserializes the `response` of the method `method_name`, adding the method name
+/// (and, with the `trace_response` feature, the JSON) to the error.
+pub(crate) fn decode<T: DeserializeOwned>(method_name: &str, response: Value) -> Result<T> {
+    from_response::<T>(&response).map_err(|source| Error::Decode {
+        method: method_name.into(),
+        source,
+        #[cfg(feature = "trace_response")]
+        json: Some(response.to_string()),
+        #[cfg(not(feature = "trace_response"))]
+        json: None,
+    })
+}
+
+/// Deserializes the `response`, hinting at the array-instead-of-object mismatch
+/// (e.g. when a single object is requested from a method returning a list).
+fn from_response<T: DeserializeOwned>(response: &Value) -> serde_json::Result<T> {
+    let len = response.as_array().map(Vec::len);
+    T::deserialize(response).map_err(|e| match len {
+        Some(len) if is_shape_mismatch(&e) => serde::de::Error::custom(format!(
+            "{} (expected an object, response was an array of {} elements)",
+            e, len
+        )),
+        _ => e,
+    })
+}
+
+fn is_shape_mismatch(e: &serde_json::Error) -> bool {
+    let msg = e.to_string();
+    msg.starts_with("invalid type: sequence") || msg.starts_with("invalid length")
+}
+
+/// Computes the request signature: md5 of `/method/{method_name}?{query}` followed by the `secret`,
+/// where `query` lists the (sorted) parameters without URL encoding.
+fn sign(method_name: &str, query: &BTreeMap<String, String>, secret: &str) -> String {
+    let query = query
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join("&");
+    let payload = format!("/method/{}?{}{}", method_name, query, secret);
+    format!("{:x}", md5::compute(payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockServer, Response};
+
+    #[test]
+    fn sign_known_params() {
+        let query: BTreeMap<String, String> = vec![
+            ("user_ids", "1,2"),
+            ("message", "hello"),
+            ("v", "5.103"),
+            ("access_token", "token"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        assert_eq!(
+            sign("secure.sendNotification", &query, "secret"),
+            "5e52557c0778c116724ef3cc832e0773"
+        );
+    }
+
+    #[test]
+    fn parse_envelope_response() {
+        let res: Vec<i64> = parse_envelope(serde_json::json!({"response": [1, 2]})).unwrap();
+        assert_eq!(res, vec![1, 2]);
+
+        let raw = parse_envelope_raw(serde_json::json!({"response": {"id": 1}})).unwrap();
+        assert_eq!(raw, serde_json::json!({"id": 1}));
+    }
+
+    #[test]
+    fn parse_envelope_error() {
+        let res = parse_envelope::<i64>(serde_json::json!({
+            "error": {"error_code": 5, "error_msg": "User authorization failed"}
+        }));
+        match res {
+            Err(crate::error::Error::API(e)) => assert_eq!(e.code(), 5),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn parse_envelope_response_and_error() {
+        let res = parse_envelope_raw(serde_json::json!({
+            "response": 1,
+            "error": {"error_code": 10, "error_msg": "Internal server error"}
+        }));
+        match res {
+            Err(crate::error::Error::API(e)) => assert_eq!(e.code(), 10),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn parse_envelope_malformed() {
+        for value in [
+            serde_json::json!([1]),
+            serde_json::json!({"something": 1}),
+            serde_json::json!({"error": "not an error object"}),
+        ]
+        .iter()
+        {
+            assert!(parse_envelope_raw(value.clone()).is_err());
+        }
+        assert!(parse_envelope::<i64>(serde_json::json!({"response": "1.5"})).is_err());
+    }
+
+    #[test]
+    fn response_array_instead_of_object() {
+        let err = from_response::<HashMap<String, i64>>(&serde_json::json!([{"id": 1}, {"id": 2}]))
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected an object, response was an array of 2 elements"));
+
+        let err = from_response::<Vec<String>>(&serde_json::json!([1])).unwrap_err();
+        assert!(!err.to_string().contains("response was an array"));
+    }
+
+    #[test]
+    fn params_insert_typed() {
+        let mut params = Params::new();
+        params.insert_bool("extended", true);
+        params.insert_bool("need_mutual", false);
+        params.insert_num("count", 100);
+        assert_eq!(params["extended"], "1");
+        assert_eq!(params["need_mutual"], "0");
+        assert_eq!(params["count"], "100");
+    }
+
+    #[tokio::test]
+    async fn captcha_image() {
+        let server =
+            MockServer::start(|_| Response::bytes("image/jpeg", b"\xff\xd8jpeg".to_vec())).await;
+        let api = APIClient::new("token");
+        let captcha = CaptchaChallenge {
+            sid: "1".into(),
+            img: server.url() + "captcha.php?sid=1",
+        };
+
+        let (bytes, content_type) = api.captcha_image(&captcha).await.unwrap();
+        assert_eq!(bytes, b"\xff\xd8jpeg");
+        assert_eq!(content_type.as_deref(), Some("image/jpeg"));
+        assert_eq!(server.requests()[0].params["sid"], "1");
+    }
+
+    #[tokio::test]
+    async fn call_method_with_version() {
+        let server = MockServer::json(serde_json::json!({"response": 1})).await;
+        let api = APIClient::builder("token")
+            .base_url(server.base_url())
+            .build();
+
+        let res: i64 = api
+            .call_method_with_version("users.get", Params::new(), "5.131")
+            .await
+            .unwrap();
+        assert_eq!(res, 1);
+        assert_eq!(server.requests()[0].params["v"], "5.131");
+    }
+
+    #[tokio::test]
+    async fn call_method_with_captcha() {
+        let server = MockServer::json(serde_json::json!({"response": 1})).await;
+        let api = APIClient::builder("token")
+            .base_url(server.base_url())
+            .build();
+
+        let mut params = Params::new();
+        params.insert("message".into(), "hi".into());
+        let res: i64 = api
+            .call_method_with_captcha("wall.post", params, "238000712", "q4ster")
+            .await
+            .unwrap();
+        assert_eq!(res, 1);
+        let request = &server.requests()[0];
+        assert_eq!(request.params["captcha_sid"], "238000712");
+        assert_eq!(request.params["captcha_key"], "q4ster");
+        assert_eq!(request.params["message"], "hi");
+    }
+
+    #[test]
+    fn debug_redacts_credentials() {
+        let builder = APIClient::builder("s3cr3t-token").secret("s3cr3t-secret");
+        let builder_debug = format!("{:?}", builder);
+        let api_debug = format!("{:?}", builder.build());
+        for debug in &[builder_debug, api_debug] {
+            assert!(!debug.contains("s3cr3t"), "{}", debug);
+            assert!(debug.contains(r#"token: "<redacted>""#), "{}", debug);
+            assert!(debug.contains(r#"secret: Some("<redacted>")"#), "{}", debug);
+        }
+        assert!(!format!("{:?}", APIClient::new("token")).contains("secret: Some"));
+    }
+
+    #[test]
+    fn build_with_min_tls_version() {
+        let api = APIClient::builder("token")
+            .min_tls_version(reqwest::tls::Version::TLS_1_2)
+            .build();
+        assert_eq!(api.base_url, DEFAULT_BASE_URL);
+    }
+
+    #[tokio::test]
+    async fn clone_with_token() {
+        let server = MockServer::json(serde_json::json!({"response": 1})).await;
+        let api = APIClient::builder("first")
+            .base_url(server.base_url())
+            .build();
+        let other = api.clone_with_token("second");
+
+        let _: i64 = api.call_method("users.get", Params::new()).await.unwrap();
+        let _: i64 = other.call_method("users.get", Params::new()).await.unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests[0].params["access_token"], "first");
+        assert_eq!(requests[1].params["access_token"], "second");
+        assert_eq!(requests[1].path, "/method/users.get");
+    }
+
+    #[tokio::test]
+    async fn call_method_with_header() {
+        let server = MockServer::json(serde_json::json!({"response": 1})).await;
+        let api = APIClient::builder("token")
+            .base_url(server.base_url())
+            .build();
+
+        let res: i64 = api
+            .call_method_with("users.get", Params::new(), |builder| {
+                builder.header("X-Request-Id", "42")
+            })
+            .await
+            .unwrap();
+        assert_eq!(res, 1);
+        let request = &server.requests()[0];
+        assert_eq!(request.headers["x-request-id"], "42");
+        assert_eq!(request.params["access_token"], "token");
+    }
+
+    #[tokio::test]
+    async fn call_method_detailed_http_method() {
+        let server = MockServer::json(serde_json::json!({"response": 1})).await;
+        let api = APIClient::builder("token")
+            .base_url(server.base_url())
+            .build();
+
+        let mut params = Params::new();
+        params.insert("user_ids".into(), "1".into());
+        let small = api
+            .call_method_detailed::<i64>("users.get", params)
+            .await
+            .unwrap();
+        assert_eq!(small.http_method, Method::GET);
+
+        let mut params = Params::new();
+        let ids: Vec<_> = (1..1000).map(|id| id.to_string()).collect();
+        params.insert("user_ids".into(), ids.join(","));
+        let large = api
+            .call_method_detailed::<i64>("users.get", params)
+            .await
+            .unwrap();
+        assert_eq!(large.http_method, Method::POST);
+        assert_eq!(large.response, 1);
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[1].method, "POST");
+        assert_eq!(requests[1].params["user_ids"], ids.join(","));
+        assert_eq!(requests[1].params["access_token"], "token");
+    }
+
+    #[tokio::test]
+    async fn retry_too_many_requests() {
+        let server = MockServer::start(|_| {
+            Response::json(serde_json::json!({
+                "error": {"error_code": 6, "error_msg": "Too many requests per second", "request_params": []}
+            }))
+        })
+        .await;
+        let api = APIClient::builder("token")
+            .base_url(server.base_url())
+            .retry(2, Backoff::Fixed(Duration::from_millis(10)))
+            .build();
+
+        match api.call_method::<Value>("users.get", Params::new()).await {
+            Err(Error::API(e)) => assert_eq!(e.code(), 6),
+            _ => unreachable!(),
+        }
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[test]
+    fn backoff_delays() {
+        let ms = Duration::from_millis;
+        let delays =
+            |backoff: Backoff| (0..4).map(|retry| backoff.delay(retry)).collect::<Vec<_>>();
+
+        assert_eq!(delays(Backoff::Fixed(ms(350))), vec![ms(350); 4]);
+        assert_eq!(
+            delays(Backoff::Exponential {
+                base: ms(100),
+                factor: 3.0,
+                max: ms(1000)
+            }),
+            vec![ms(100), ms(300), ms(900), ms(1000)]
+        );
+        assert_eq!(
+            delays(Backoff::Custom(Box::new(move |retry| ms(50) * (retry + 1)))),
+            vec![ms(50), ms(100), ms(150), ms(200)]
+        );
+        let huge = Backoff::Exponential {
+            base: ms(1),
+            factor: 1e300,
+            max: ms(5),
+        };
+        assert_eq!(huge.delay(u32::MAX), ms(5));
+    }
+
+    #[tokio::test]
+    async fn deadline_across_retries() {
+        let server = MockServer::start(|_| {
+            Response::json(serde_json::json!({
+                "error": {"error_code": 6, "error_msg": "Too many requests per second", "request_params": []}
+            }))
+        })
+        .await;
+        let deadline = Duration::from_millis(500);
+        let api = APIClient::builder("token")
+            .base_url(server.base_url())
+            .retry(100, Backoff::Fixed(RETRY_DELAY))
+            .deadline(deadline)
+            .build();
+
+        let started = Instant::now();
+        match api.call_method::<Value>("users.get", Params::new()).await {
+            Err(Error::DeadlineExceeded(d)) => assert_eq!(d, deadline),
+            _ => unreachable!(),
+        }
+        assert!(started.elapsed() < deadline);
+        // sent at 0 and at RETRY_DELAY, giving up before the third attempt
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn deadline_keeps_shorter_timeouts() {
+        // accepts the connections, but never responds
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}/method/", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                connections.push(stream);
+            }
+        });
+        let timeout = Duration::from_millis(100);
+        let deadline = Duration::from_secs(10);
+
+        let api = APIClient::builder("token")
+            .base_url(base_url.clone())
+            .timeout(timeout)
+            .deadline(deadline)
+            .build();
+        let started = Instant::now();
+        match api.call_method::<Value>("users.get", Params::new()).await {
+            Err(Error::Request(e)) => assert!(e.is_timeout()),
+            _ => unreachable!(),
+        }
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        let api = APIClient::builder("token")
+            .base_url(base_url)
+            .deadline(deadline)
+            .build();
+        let started = Instant::now();
+        let result = api
+            .call_method_with::<Value, _>("users.get", Params::new(), |builder| {
+                builder.timeout(timeout)
+            })
+            .await;
+        match result {
+            Err(Error::Request(e)) => assert!(e.is_timeout()),
+            _ => unreachable!(),
+        }
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn decode_error_names_method() {
+        let server = MockServer::json(serde_json::json!({"response": "not a number"})).await;
+        let api = APIClient::builder("token")
+            .base_url(server.base_url())
+            .build();
+
+        let err = api
+            .call_method::<i64>("users.get", Params::new())
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("failed to decode response for users.get: "));
+        match err {
+            Error::Decode { method, .. } => assert_eq!(method, "users.get"),
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn call_method_traced_writes_files() {
+        let server = MockServer::json(serde_json::json!({"response": "not a number"})).await;
+        let dir = std::env::temp_dir().join(format!("rvk-traced-{}", std::process::id()));
+        let api = APIClient::builder("token")
+            .base_url(server.base_url())
+            .build();
+
+        assert!(api
+            .call_method_traced::<i64>("users.get", Params::new(), &dir)
+            .await
+            .is_err());
+
+        let files: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        let json = files
+            .iter()
+            .find(|path| path.extension() == Some("json".as_ref()))
+            .unwrap();
+        assert!(json.to_string_lossy().ends_with("_users.get.json"));
+        assert!(std::fs::read_to_string(json)
+            .unwrap()
+            .contains("not a number"));
+        assert!(files
+            .iter()
+            .any(|path| path.to_string_lossy().ends_with("_msg.txt")));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "trace_response")]
+    #[tokio::test]
+    async fn trace_into_builder_dir() {
+        let server = MockServer::json(serde_json::json!({"response": "not a number"})).await;
+        let dir = std::env::temp_dir().join(format!("rvk-trace-{}", std::process::id()));
+        let api = APIClient::builder("token")
+            .base_url(server.base_url())
+            .trace_dir(&dir)
+            .trace_all(true)
+            .build();
+
+        assert!(api
+            .call_method::<i64>("users.get", Params::new())
+            .await
+            .is_err());
+        assert!(api
+            .call_method::<String>("users.get", Params::new())
+            .await
+            .is_ok());
+
+        for subdir in &["failed", "succeeded"] {
+            let traced = std::fs::read_dir(dir.join(subdir))
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .find(|path| path.extension() == Some("json".as_ref()))
+                .unwrap();
+            let response = std::fs::read_to_string(traced).unwrap();
+            assert!(response.contains("not a number"));
         }
+        std::fs::remove_dir_all(dir).unwrap();
     }
 }
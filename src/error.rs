@@ -2,7 +2,7 @@
 
 use serde_derive::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::{collections::HashMap, convert::TryFrom};
 
 /// Convenience type for defining `Result`s.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -40,6 +40,19 @@ impl APIError {
         self.error_code
     }
 
+    /// Returns the code of this `APIError` as an [`ErrorCode`](enum.ErrorCode.html).
+    ///
+    /// ```
+    /// # use rvk::error::{APIError, ErrorCode};
+    /// # use std::collections::HashMap;
+    ///
+    /// let err = APIError::new(6, "Too many requests per second".into(), HashMap::new());
+    /// assert_eq!(err.kind(), ErrorCode::TooManyRequests);
+    /// ```
+    pub fn kind(&self) -> ErrorCode {
+        self.error_code.into()
+    }
+
     /// Returns the message of this `APIError`.
     ///
     /// ```
@@ -66,6 +79,197 @@ impl APIError {
     pub fn extra(&self) -> &HashMap<String, Value> {
         &self.extra
     }
+
+    /// Returns the captcha to enter, if this is a "Captcha needed" error (#14).
+    ///
+    /// ```
+    /// # use rvk::error::APIError;
+    /// # use std::collections::HashMap;
+    ///
+    /// let err = APIError::new(0, "test".into(), HashMap::new());
+    /// assert_eq!(err.as_captcha(), None);
+    /// ```
+    pub fn as_captcha(&self) -> Option<CaptchaChallenge> {
+        if self.error_code != 14 {
+            return None;
+        }
+
+        let sid = match self.extra.get("captcha_sid")? {
+            Value::String(s) => s.clone(),
+            v => v.to_string(),
+        };
+        let img = self.extra.get("captcha_img")?.as_str()?.to_owned();
+        Some(CaptchaChallenge { sid, img })
+    }
+
+    /// Returns the validation to pass, if this is a "Validation required" error (#17).
+    ///
+    /// ```
+    /// # use rvk::error::APIError;
+    /// # use std::collections::HashMap;
+    ///
+    /// let err = APIError::new(0, "test".into(), HashMap::new());
+    /// assert_eq!(err.as_validation(), None);
+    /// ```
+    pub fn as_validation(&self) -> Option<ValidationChallenge> {
+        if self.error_code != 17 {
+            return None;
+        }
+
+        let redirect_uri = self.extra.get("redirect_uri")?.as_str()?.to_owned();
+        Some(ValidationChallenge { redirect_uri })
+    }
+}
+
+/// The codes of the API errors: <https://vk.com/dev/errors>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// #1
+    Unknown,
+    /// #2
+    AppDisabled,
+    /// #3
+    UnknownMethod,
+    /// #4
+    InvalidSignature,
+    /// #5
+    AuthorizationFailed,
+    /// #6
+    TooManyRequests,
+    /// #7
+    PermissionDenied,
+    /// #8
+    InvalidRequest,
+    /// #9
+    FloodControl,
+    /// #10
+    InternalServerError,
+    /// #14
+    CaptchaNeeded,
+    /// #15
+    AccessDenied,
+    /// #17
+    ValidationRequired,
+    /// #18
+    UserDeletedOrBanned,
+    /// #29
+    RateLimitReached,
+    /// #30
+    PrivateProfile,
+    /// #100
+    InvalidParameter,
+    /// #113
+    InvalidUserId,
+    /// #200
+    AlbumAccessDenied,
+    /// #203
+    GroupAccessDenied,
+    /// Any other code
+    Other(u64),
+}
+
+impl ErrorCode {
+    /// Returns the numeric code.
+    pub fn code(&self) -> u64 {
+        match *self {
+            ErrorCode::Unknown => 1,
+            ErrorCode::AppDisabled => 2,
+            ErrorCode::UnknownMethod => 3,
+            ErrorCode::InvalidSignature => 4,
+            ErrorCode::AuthorizationFailed => 5,
+            ErrorCode::TooManyRequests => 6,
+            ErrorCode::PermissionDenied => 7,
+            ErrorCode::InvalidRequest => 8,
+            ErrorCode::FloodControl => 9,
+            ErrorCode::InternalServerError => 10,
+            ErrorCode::CaptchaNeeded => 14,
+            ErrorCode::AccessDenied => 15,
+            ErrorCode::ValidationRequired => 17,
+            ErrorCode::UserDeletedOrBanned => 18,
+            ErrorCode::RateLimitReached => 29,
+            ErrorCode::PrivateProfile => 30,
+            ErrorCode::InvalidParameter => 100,
+            ErrorCode::InvalidUserId => 113,
+            ErrorCode::AlbumAccessDenied => 200,
+            ErrorCode::GroupAccessDenied => 203,
+            ErrorCode::Other(code) => code,
+        }
+    }
+
+    /// Whether the same call may succeed if repeated later: the transient and server-side errors.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ErrorCode::Unknown | ErrorCode::TooManyRequests | ErrorCode::InternalServerError
+        )
+    }
+
+    /// Whether the error is caused by what the user asked for (an invalid id or parameter,
+    /// no access to the object, etc.) and so is worth showing to them,
+    /// rather than by the app or the infrastructure.
+    pub fn is_user_facing(&self) -> bool {
+        matches!(
+            self,
+            ErrorCode::PermissionDenied
+                | ErrorCode::AccessDenied
+                | ErrorCode::UserDeletedOrBanned
+                | ErrorCode::PrivateProfile
+                | ErrorCode::InvalidParameter
+                | ErrorCode::InvalidUserId
+                | ErrorCode::AlbumAccessDenied
+                | ErrorCode::GroupAccessDenied
+        )
+    }
+}
+
+impl From<u64> for ErrorCode {
+    fn from(code: u64) -> Self {
+        match code {
+            1 => ErrorCode::Unknown,
+            2 => ErrorCode::AppDisabled,
+            3 => ErrorCode::UnknownMethod,
+            4 => ErrorCode::InvalidSignature,
+            5 => ErrorCode::AuthorizationFailed,
+            6 => ErrorCode::TooManyRequests,
+            7 => ErrorCode::PermissionDenied,
+            8 => ErrorCode::InvalidRequest,
+            9 => ErrorCode::FloodControl,
+            10 => ErrorCode::InternalServerError,
+            14 => ErrorCode::CaptchaNeeded,
+            15 => ErrorCode::AccessDenied,
+            17 => ErrorCode::ValidationRequired,
+            18 => ErrorCode::UserDeletedOrBanned,
+            29 => ErrorCode::RateLimitReached,
+            30 => ErrorCode::PrivateProfile,
+            100 => ErrorCode::InvalidParameter,
+            113 => ErrorCode::InvalidUserId,
+            200 => ErrorCode::AlbumAccessDenied,
+            203 => ErrorCode::GroupAccessDenied,
+            code => ErrorCode::Other(code),
+        }
+    }
+}
+
+/// A validation (e.g. 2FA or SMS confirmation) required by the API to proceed.
+///
+/// Open the `redirect_uri` for the user to pass the validation, then repeat the call.
+/// Unlike an invalid token (error #5), the token stays valid.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ValidationChallenge {
+    /// The URL of the validation page.
+    pub redirect_uri: String,
+}
+
+/// A captcha required by the API to proceed.
+///
+/// Fetch the image with [`APIClient::captcha_image`](../api/struct.APIClient.html#method.captcha_image),
+/// then repeat the call with the `captcha_sid` and `captcha_key` (the text from the image) parameters.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CaptchaChallenge {
+    /// The `captcha_sid` parameter.
+    pub sid: String,
+    /// The URL of the captcha image.
+    pub img: String,
 }
 
 /// A generic error.
@@ -83,11 +287,44 @@ pub enum Error {
     #[error("Serialization/Deserialization error: {0}")]
     Serde(#[from] serde_json::error::Error),
 
+    /// Errors decoding the response of the method `method`.
+    #[error("failed to decode response for {method}: {source}")]
+    Decode {
+        method: String,
+        source: serde_json::error::Error,
+        /// The JSON which failed to decode (only with the `trace_response` feature).
+        json: Option<String>,
+    },
+
+    /// The call has not completed within the deadline set with
+    /// [`APIClientBuilder::deadline`](../api/struct.APIClientBuilder.html#method.deadline).
+    #[error("Deadline of {0:?} exceeded")]
+    DeadlineExceeded(std::time::Duration),
+
     /// Other errors.
     #[error("Other error: {0}")]
     Other(String),
 }
 
+impl Error {
+    /// Returns the code of the API error, or `None` for the other errors
+    /// and for the codes that do not fit in an `i32`.
+    ///
+    /// ```
+    /// # use rvk::error::{APIError, Error};
+    /// # use std::collections::HashMap;
+    ///
+    /// let err: Error = APIError::new(6, "Too many requests per second".into(), HashMap::new()).into();
+    /// assert_eq!(err.code(), Some(6));
+    /// ```
+    pub fn code(&self) -> Option<i32> {
+        match self {
+            Error::API(e) => i32::try_from(e.code()).ok(),
+            _ => None,
+        }
+    }
+}
+
 impl From<APIError> for Error {
     fn from(e: APIError) -> Error {
         Error::API(e)
@@ -121,10 +358,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn error_code_round_trip() {
+        for code in 0..1000 {
+            assert_eq!(ErrorCode::from(code).code(), code);
+        }
+        assert_eq!(ErrorCode::from(113), ErrorCode::InvalidUserId);
+        assert_eq!(ErrorCode::from(42), ErrorCode::Other(42));
+    }
+
+    #[test]
+    fn error_code_user_facing() {
+        for code in &[7, 15, 18, 30, 100, 113, 200, 203] {
+            assert!(ErrorCode::from(*code).is_user_facing(), "{}", code);
+        }
+        for code in &[1, 5, 6, 10, 14, 42] {
+            assert!(!ErrorCode::from(*code).is_user_facing(), "{}", code);
+        }
+    }
+
+    #[test]
+    fn error_code_retryable() {
+        assert!(ErrorCode::TooManyRequests.is_retryable());
+        assert!(ErrorCode::InternalServerError.is_retryable());
+        assert!(!ErrorCode::InvalidParameter.is_retryable());
+        assert!(!ErrorCode::AuthorizationFailed.is_retryable());
+    }
+
     #[test]
     fn other_error_from_str() {
         let other_err = "error";
-        let err: Error = other_err.clone().into();
+        let err: Error = other_err.into();
 
         match err {
             Error::Other(s) => assert_eq!(s, other_err),
@@ -142,4 +406,67 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn code_of_api_error() {
+        let err: Error = APIError::new(6, "test".to_string(), HashMap::new()).into();
+        assert_eq!(err.code(), Some(6));
+        let err: Error = APIError::new(1 << 32, "test".to_string(), HashMap::new()).into();
+        assert_eq!(err.code(), None);
+    }
+
+    #[test]
+    fn code_of_request_error() {
+        let err: Error = reqwest::Client::new()
+            .get("not a url")
+            .build()
+            .unwrap_err()
+            .into();
+        assert_eq!(err.code(), None);
+    }
+
+    #[test]
+    fn captcha_challenge() {
+        let err: APIError = serde_json::from_str(
+            r#"
+            {
+                "error_code": 14,
+                "error_msg": "Captcha needed",
+                "captcha_sid": "548470705643",
+                "captcha_img": "https://api.vk.com/captcha.php?sid=548470705643"
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            err.as_captcha(),
+            Some(CaptchaChallenge {
+                sid: "548470705643".into(),
+                img: "https://api.vk.com/captcha.php?sid=548470705643".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn validation_challenge() {
+        let err: APIError = serde_json::from_str(
+            r#"
+            {
+                "error_code": 17,
+                "error_msg": "Validation required: please open redirect_uri in browser",
+                "redirect_uri": "https://m.vk.com/login?act=security_check&api_hash=abc"
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            err.as_validation(),
+            Some(ValidationChallenge {
+                redirect_uri: "https://m.vk.com/login?act=security_check&api_hash=abc".into(),
+            })
+        );
+        assert_eq!(err.as_captcha(), None);
+    }
 }
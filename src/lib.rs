@@ -29,7 +29,9 @@
 //!
 //!             println!(
 //!                 "User #{} is {} {}.",
-//!                 user.id, user.first_name, user.last_name
+//!                 user.id,
+//!                 user.first_name.as_deref().unwrap_or_default(),
+//!                 user.last_name.as_deref().unwrap_or_default()
 //!             );
 //!         }
 //!         Err(e) => println!("{}", e),
@@ -41,8 +43,13 @@ pub mod api;
 pub mod error;
 pub mod methods;
 pub mod objects;
+pub mod pool;
 
-pub use crate::api::{APIClient, Params};
+#[cfg(test)]
+mod mock;
+
+pub use crate::api::{APIClient, APIClientBuilder, Params, ParamsExt};
+pub use crate::objects::{Boolean, Integer, Number};
 
 /// Defines the version of VK API that is used.
 pub const API_VERSION: &str = "5.103";
@@ -9,19 +9,21 @@
 //! ## Note: `execute`
 //! The `execute` method has no category, so its path is `rvk::methods::execute`.
 //!
+//! To send several calls in one request, collect them as [`MethodCall`](struct.MethodCall.html)s
+//! into an [`ExecuteBuilder`](struct.ExecuteBuilder.html).
+//!
 //! ## Note: `photos.move`
 //! Since `move` is a Rust keyword, the function for calling `photos.move` API method is `rvk::methods::photos::move_` (**with the underscore!**)
 
 macro_rules! api_category {
     ($category:expr; methods { $($name:ident),* }) => {
         use heck::MixedCase;
-        use std::collections::HashMap;
         use lazy_static::lazy_static;
         const CATEGORY: &str = $category;
 
         lazy_static! {
-            static ref METHOD_NAMES: HashMap<&'static str, String> = {
-                let mut m = HashMap::new();
+            static ref METHOD_NAMES: std::collections::HashMap<&'static str, String> = {
+                let mut m = std::collections::HashMap::new();
 
                 $(
                     m.insert(stringify!($name), CATEGORY.to_owned() + "." + &stringify!($name).to_mixed_case());
@@ -56,8 +58,238 @@ macro_rules! api_method {
     };
 }
 
+use crate::{
+    api::{decode, APIClient},
+    error::{Error, Result},
+    objects::{response::ListResponse, Integer},
+    Params, ParamsExt,
+};
+use futures_util::stream::{self, Stream, TryStreamExt};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
 api_method!(execute, "execute");
 
+/// Streams the raw pages of any `{count, items}` method, requesting `count` items per page
+/// and advancing the `offset_key` parameter (e.g. `offset`) by the number of the items received,
+/// until a short or empty page is received or the `count` of the page is reached.
+///
+/// A non-positive `count` is treated as `1`.
+///
+/// ```no_run
+/// # use futures_util::TryStreamExt;
+/// # use rvk::{methods::page_stream, APIClient, Params};
+/// # async fn run(api: APIClient) -> rvk::error::Result<()> {
+/// let mut params = Params::new();
+/// params.insert("user_id".into(), "1".into());
+/// let pages: Vec<_> = page_stream(&api, "friends.get", params, "offset", 5000)
+///     .try_collect()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn page_stream<'a>(
+    api: &'a APIClient,
+    method: &'a str,
+    params: Params,
+    offset_key: &'a str,
+    count: Integer,
+) -> impl Stream<Item = Result<Value>> + 'a {
+    let count = count.max(1);
+    stream::try_unfold(Some(0), move |offset| {
+        let mut params = params.clone();
+        async move {
+            let offset = match offset {
+                Some(offset) => offset,
+                None => return Ok::<_, Error>(None),
+            };
+            params.insert_num(offset_key, offset);
+            params.insert_num("count", count);
+
+            let page: Value = api.call_method(method, params).await?;
+            let received = page["items"].as_array().map_or(0, Vec::len) as Integer;
+            let total = page["count"].as_i64();
+            let next = if received == 0
+                || received < count
+                || matches!(total, Some(total) if offset + received >= total)
+            {
+                None
+            } else {
+                Some(offset + received)
+            };
+            Ok(Some((page, next)))
+        }
+    })
+}
+
+/// Streams the raw pages of any cursor-paginated method (e.g. `newsfeed.get`), requesting `count` items per page
+/// and passing the `next_from` of each page as the `cursor_key` parameter (e.g. `start_from`) of the next request,
+/// until a page has no `next_from` or no items.
+///
+/// A non-positive `count` is treated as `1`.
+pub fn cursor_page_stream<'a>(
+    api: &'a APIClient,
+    method: &'a str,
+    params: Params,
+    cursor_key: &'a str,
+    count: Integer,
+) -> impl Stream<Item = Result<Value>> + 'a {
+    let count = count.max(1);
+    stream::try_unfold(Some(None), move |cursor: Option<Option<String>>| {
+        let mut params = params.clone();
+        async move {
+            let cursor = match cursor {
+                Some(cursor) => cursor,
+                None => return Ok::<_, Error>(None),
+            };
+            if let Some(cursor) = cursor {
+                params.insert(cursor_key.into(), cursor);
+            }
+            params.insert_num("count", count);
+
+            let page: Value = api.call_method(method, params).await?;
+            let received = page["items"].as_array().map_or(0, Vec::len);
+            let next = match page["next_from"].as_str() {
+                Some(next_from) if !next_from.is_empty() && received > 0 => {
+                    Some(Some(next_from.to_string()))
+                }
+                _ => None,
+            };
+            Ok(Some((page, next)))
+        }
+    })
+}
+
+/// Streams the items of a `{count, items}` method, requesting `count` items per page
+/// and advancing `offset` until a short page is received.
+pub(crate) fn list_stream<'a, T: DeserializeOwned + 'a>(
+    api: &'a APIClient,
+    method: &'a str,
+    params: Params,
+    count: Integer,
+) -> impl Stream<Item = Result<T>> + 'a {
+    page_stream(api, method, params, "offset", count)
+        .and_then(move |page| async move { decode::<ListResponse<T>>(method, page) })
+        .map_ok(|page| stream::iter(page.items.into_iter().map(Ok)))
+        .try_flatten()
+}
+
+/// Fetches the media of each of the `items` (at most `concurrency` at a time, but at least one),
+/// yielding the items, in order, along with the bytes of their media.
+///
+/// The `select` function chooses the URL of the media to fetch (e.g. with `photo::Photo::largest()`);
+/// the items it chooses none for are yielded with no bytes.
+pub fn fetch_media<'a, T, S, F>(
+    api: &'a APIClient,
+    items: S,
+    concurrency: usize,
+    select: F,
+) -> impl Stream<Item = Result<(T, Vec<u8>)>> + 'a
+where
+    T: 'a,
+    S: Stream<Item = Result<T>> + 'a,
+    F: Fn(&T) -> Option<String> + 'a,
+{
+    items
+        .map_ok(move |item| {
+            let url = select(&item);
+            async move {
+                let bytes = match url {
+                    Some(url) => api.fetch(&url).await?.bytes().await?.to_vec(),
+                    None => Vec::new(),
+                };
+                Ok((item, bytes))
+            }
+        })
+        .try_buffered(concurrency.max(1))
+}
+
+/// An API method call stored as data, to be performed later.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodCall {
+    /// The method name, e.g. `users.get`.
+    pub name: String,
+    pub params: Params,
+}
+
+impl MethodCall {
+    /// Creates a new `MethodCall`, given the method name and its parameters.
+    pub fn new(name: impl Into<String>, params: Params) -> Self {
+        Self {
+            name: name.into(),
+            params,
+        }
+    }
+
+    /// Performs the call on its own.
+    pub async fn execute<T: DeserializeOwned>(&self, api: &APIClient) -> Result<T> {
+        api.call_method(&self.name, self.params.clone()).await
+    }
+}
+
+/// Batches several [`MethodCall`](struct.MethodCall.html)s into a single `execute` call.
+///
+/// The response is an array with the results of the calls, in order.
+/// Note that VK allows at most 25 calls in one `execute`.
+#[derive(Debug, Clone, Default)]
+pub struct ExecuteBuilder {
+    calls: Vec<MethodCall>,
+}
+
+impl ExecuteBuilder {
+    /// Creates an empty `ExecuteBuilder`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a call to the batch.
+    pub fn call(mut self, call: MethodCall) -> Self {
+        self.calls.push(call);
+        self
+    }
+
+    /// Adds several calls to the batch.
+    pub fn calls(mut self, calls: Vec<MethodCall>) -> Self {
+        self.calls.extend(calls);
+        self
+    }
+
+    /// Returns the VKScript code performing the calls.
+    pub fn code(&self) -> String {
+        let calls = self
+            .calls
+            .iter()
+            .map(|call| {
+                let params: BTreeMap<_, _> = call.params.iter().collect();
+                format!(
+                    "API.{}({})",
+                    call.name,
+                    serde_json::to_string(&params).unwrap_or_default()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("return [{}];", calls)
+    }
+
+    /// Performs the calls via the `execute` method.
+    pub async fn execute<T: DeserializeOwned>(&self, api: &APIClient) -> Result<T> {
+        let mut params = Params::new();
+        params.insert("code".into(), self.code());
+        execute(api, params).await
+    }
+}
+
+/// Joins the `items` into a comma-separated list, as VK expects for array parameters.
+pub(crate) fn csv<T: ToString>(items: &[T]) -> String {
+    items
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 pub mod account;
 pub mod ads;
 pub mod app_widgets;
@@ -94,3 +326,217 @@ pub mod utils;
 pub mod video;
 pub mod wall;
 pub mod widgets;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockServer, Response};
+    use serde_json::json;
+
+    fn users_get(user_ids: &str) -> MethodCall {
+        let mut params = Params::new();
+        params.insert("user_ids".into(), user_ids.into());
+        MethodCall::new("users.get", params)
+    }
+
+    #[tokio::test]
+    async fn method_call_execute() {
+        let server = MockServer::json(json!({"response": [{"id": 1}]})).await;
+        let api = APIClient::builder("token")
+            .base_url(server.base_url())
+            .build();
+
+        let call = users_get("1");
+        let res: serde_json::Value = call.execute(&api).await.unwrap();
+        assert_eq!(res, json!([{"id": 1}]));
+
+        let requests = server.requests();
+        assert_eq!(requests[0].path, "/method/users.get");
+        assert_eq!(requests[0].params["user_ids"], "1");
+    }
+
+    #[tokio::test]
+    async fn fetch_media_zero_concurrency() {
+        let server = MockServer::start(|request| {
+            Response::bytes("image/jpeg", request.path.as_bytes().to_vec())
+        })
+        .await;
+        let api = APIClient::new("token");
+
+        let media_url = server.url();
+        let items = stream::iter(vec![Ok(1), Ok(2)]);
+        let fetched = fetch_media(&api, items, 0, move |item: &Integer| {
+            Some(format!("{}{}.jpg", media_url, item))
+        })
+        .try_collect::<Vec<_>>();
+        let fetched = tokio::time::timeout(std::time::Duration::from_secs(5), fetched)
+            .await
+            .expect("fetch_media hangs")
+            .unwrap();
+
+        assert_eq!(
+            fetched,
+            vec![(1, b"/1.jpg".to_vec()), (2, b"/2.jpg".to_vec())]
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_media_of_feed() {
+        let server = MockServer::start(|request| match request.path.as_str() {
+            "/method/newsfeed.get" => Response::json(json!({"response": {
+                "items": [
+                    {
+                        "type": "post", "source_id": 1, "date": 1600000000,
+                        "attachments": [{"type": "photo", "photo": {"id": 1, "sizes": [
+                            {"url": "http://127.0.0.1/ignored", "width": 1, "height": 1, "type": "s"},
+                            {"url": "/media/1.jpg", "width": 604, "height": 453, "type": "x"}
+                        ]}}]
+                    },
+                    {"type": "post", "source_id": 2, "date": 1600000001}
+                ]
+            }})),
+            path => Response::bytes("image/jpeg", path.as_bytes().to_vec()),
+        })
+        .await;
+        let api = APIClient::builder("token")
+            .base_url(server.base_url())
+            .build();
+
+        let media_url = server.url();
+        let select = move |item: &crate::objects::newsfeed::Item| {
+            let photo = item
+                .attachments
+                .as_ref()?
+                .iter()
+                .find_map(|a| a.photo.as_ref())?;
+            let url = photo.largest()?.url.as_ref()?;
+            Some(media_url.clone() + url.trim_start_matches('/'))
+        };
+        let fetched: Vec<_> =
+            fetch_media(&api, newsfeed::get_stream(&api, Params::new()), 2, select)
+                .try_collect()
+                .await
+                .unwrap();
+
+        assert_eq!(fetched.len(), 2);
+        assert_eq!(fetched[0].0.source_id, 1);
+        assert_eq!(fetched[0].1, b"/media/1.jpg");
+        assert_eq!(fetched[1].0.source_id, 2);
+        assert!(fetched[1].1.is_empty());
+    }
+
+    #[test]
+    fn execute_builder_code() {
+        let builder = ExecuteBuilder::new().calls(vec![users_get("1"), users_get("2")]);
+        assert_eq!(
+            builder.code(),
+            r#"return [API.users.get({"user_ids":"1"}),API.users.get({"user_ids":"2"})];"#
+        );
+    }
+
+    #[tokio::test]
+    async fn page_stream_two_pages() {
+        let server = MockServer::start(|request| {
+            let items = match request.params["start"].as_str() {
+                "0" => json!(["a", "b"]),
+                _ => json!(["c"]),
+            };
+            Response::json(json!({"response": {"items": items}}))
+        })
+        .await;
+        let api = APIClient::builder("token")
+            .base_url(server.base_url())
+            .build();
+
+        let pages: Vec<Value> = page_stream(&api, "custom.list", Params::new(), "start", 2)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(
+            pages,
+            vec![json!({"items": ["a", "b"]}), json!({"items": ["c"]})]
+        );
+
+        let starts: Vec<_> = server
+            .requests()
+            .iter()
+            .map(|request| request.params["start"].clone())
+            .collect();
+        assert_eq!(starts, vec!["0", "2"]);
+    }
+
+    #[tokio::test]
+    async fn page_stream_stops_on_empty_page() {
+        let server = MockServer::json(json!({"response": {"items": []}})).await;
+        let api = APIClient::builder("token")
+            .base_url(server.base_url())
+            .build();
+
+        let pages: Vec<Value> = page_stream(&api, "custom.list", Params::new(), "offset", 0)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(pages, vec![json!({"items": []})]);
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].params["count"], "1");
+    }
+
+    #[tokio::test]
+    async fn cursor_page_stream_two_pages() {
+        let server = MockServer::start(|request| {
+            let page = match request.params.get("start_from").map(String::as_str) {
+                None => json!({"items": ["a", "b"], "next_from": "2/-1_2"}),
+                _ => json!({"items": ["c"], "next_from": ""}),
+            };
+            Response::json(json!({ "response": page }))
+        })
+        .await;
+        let api = APIClient::builder("token")
+            .base_url(server.base_url())
+            .build();
+
+        let pages: Vec<Value> =
+            cursor_page_stream(&api, "newsfeed.get", Params::new(), "start_from", 2)
+                .try_collect()
+                .await
+                .unwrap();
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[1]["items"], json!(["c"]));
+
+        let cursors: Vec<_> = server
+            .requests()
+            .iter()
+            .map(|request| request.params.get("start_from").cloned())
+            .collect();
+        assert_eq!(cursors, vec![None, Some("2/-1_2".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn list_stream_two_pages() {
+        let server = MockServer::start(|request| {
+            let items = match request.params["offset"].as_str() {
+                "0" => json!([1, 2]),
+                _ => json!([3]),
+            };
+            Response::json(json!({"response": {"count": 3, "items": items}}))
+        })
+        .await;
+        let api = APIClient::builder("token")
+            .base_url(server.base_url())
+            .build();
+
+        let items: Vec<Integer> = list_stream::<Integer>(&api, "friends.get", Params::new(), 2)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(items, vec![1, 2, 3]);
+
+        let offsets: Vec<_> = server
+            .requests()
+            .iter()
+            .map(|request| request.params["offset"].clone())
+            .collect();
+        assert_eq!(offsets, vec!["0", "2"]);
+    }
+}
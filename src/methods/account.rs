@@ -1,10 +1,17 @@
+use crate::{
+    api::{decode, APIClient},
+    error::Result,
+    objects::{account::Counters, Integer},
+    Params, ParamsExt,
+};
+use serde_json::Value;
+
 api_category!("account"; methods {
     ban,
     change_password,
     get_active_offers,
     get_app_permissions,
     get_banned,
-    get_counters,
     get_info,
     get_profile_info,
     get_push_settings,
@@ -12,10 +19,51 @@ api_category!("account"; methods {
     save_profile_info,
     set_info,
     set_name_in_menu,
-    set_offline,
-    set_online,
     set_push_settings,
     set_silence_mode,
     unban,
     unregister_device
 });
+
+/// Calls `account.setOnline`, marking the current user as online (for 5 minutes),
+/// optionally with `voip` calls available.
+pub async fn set_online(api: &APIClient, voip: bool) -> Result<Integer> {
+    let mut params = Params::new();
+    params.insert_bool("voip", voip);
+    api.call_method("account.setOnline", params).await
+}
+
+/// Calls `account.setOffline`, marking the current user as offline.
+pub async fn set_offline(api: &APIClient) -> Result<Integer> {
+    api.call_method("account.setOffline", Params::new()).await
+}
+
+/// Calls `account.getCounters` for the `filter` counters (e.g. `friends`, `messages`), or all of them if empty.
+pub async fn get_counters(api: &APIClient, filter: &[&str]) -> Result<Counters> {
+    let mut params = Params::new();
+    if !filter.is_empty() {
+        params.insert("filter".into(), filter.join(","));
+    }
+    let value: Value = api.call_method("account.getCounters", params).await?;
+    counters(value)
+}
+
+// VK responds with an empty array if all the counters are zero
+fn counters(value: Value) -> Result<Counters> {
+    match value {
+        Value::Array(ref items) if items.is_empty() => Ok(Counters::default()),
+        value => decode("account.getCounters", value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn counters_of_response() {
+        assert_eq!(counters(json!({"messages": 3})).unwrap().messages, Some(3));
+        assert_eq!(counters(json!([])).unwrap().messages, None);
+    }
+}
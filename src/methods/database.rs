@@ -1,15 +1,86 @@
+use super::list_stream;
+use crate::{
+    api::APIClient,
+    error::Result,
+    objects::{database::NamedObject, response::ListResponse, Integer},
+    Params, ParamsExt,
+};
+use futures_util::stream::Stream;
+
 api_category!("database"; methods {
-    get_chairs,
     get_cities,
     get_cities_by_id,
     get_countries,
     get_countries_by_id,
-    get_faculties,
     get_metro_stations,
     get_metro_stations_by_id,
     get_regions,
     get_school_classes,
-    get_schools,
     get_streets_by_id,
     get_universities
 });
+
+/// The largest `count` the `database` methods accept.
+const PAGE_SIZE: Integer = 1000;
+
+/// Calls `database.getChairs` for the faculty `faculty_id`.
+pub async fn get_chairs(
+    api: &APIClient,
+    faculty_id: Integer,
+    offset: Integer,
+    count: Integer,
+) -> Result<ListResponse<NamedObject>> {
+    let params = page_params("faculty_id", faculty_id, offset, count);
+    api.call_method("database.getChairs", params).await
+}
+
+/// Calls `database.getFaculties` for the university `university_id`.
+pub async fn get_faculties(
+    api: &APIClient,
+    university_id: Integer,
+    offset: Integer,
+    count: Integer,
+) -> Result<ListResponse<NamedObject>> {
+    let params = page_params("university_id", university_id, offset, count);
+    api.call_method("database.getFaculties", params).await
+}
+
+/// Calls `database.getSchools` for the city `city_id`.
+pub async fn get_schools(
+    api: &APIClient,
+    city_id: Integer,
+    offset: Integer,
+    count: Integer,
+) -> Result<ListResponse<NamedObject>> {
+    let params = page_params("city_id", city_id, offset, count);
+    api.call_method("database.getSchools", params).await
+}
+
+/// Streams all the cities of the country `country_id` (via `database.getCities` with `need_all=1`).
+pub fn cities_stream(
+    api: &APIClient,
+    country_id: Integer,
+) -> impl Stream<Item = Result<NamedObject>> + '_ {
+    let mut params = Params::new();
+    params.insert_num("country_id", country_id);
+    params.insert_bool("need_all", true);
+    list_stream(api, "database.getCities", params, PAGE_SIZE)
+}
+
+/// Streams all the schools of the city `city_id` (via `database.getSchools`).
+pub fn schools_stream(
+    api: &APIClient,
+    city_id: Integer,
+) -> impl Stream<Item = Result<NamedObject>> + '_ {
+    let mut params = Params::new();
+    params.insert_num("city_id", city_id);
+    list_stream(api, "database.getSchools", params, PAGE_SIZE)
+}
+
+fn page_params(key: &str, id: Integer, offset: Integer, count: Integer) -> Params {
+    let mut params = Params::new();
+    params.insert_num(key, id);
+    params.insert_num("offset", offset);
+    params.insert_num("count", count);
+    params
+}
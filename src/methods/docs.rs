@@ -1,3 +1,11 @@
+use crate::{
+    api::APIClient,
+    error::Result,
+    objects::document::{Document, SavedDocument, UploadServer},
+    objects::Integer,
+    Params, ParamsExt,
+};
+
 api_category!("docs"; methods {
     add,
     delete,
@@ -6,8 +14,37 @@ api_category!("docs"; methods {
     get_by_id,
     get_messages_upload_server,
     get_types,
-    get_upload_server,
     get_wall_upload_server,
-    save,
     search
 });
+
+/// Calls `docs.getUploadServer` for uploading a document to the current user
+/// or to the community `group_id`.
+pub async fn get_upload_server(api: &APIClient, group_id: Option<Integer>) -> Result<UploadServer> {
+    let mut params = Params::new();
+    if let Some(group_id) = group_id {
+        params.insert_num("group_id", group_id);
+    }
+    api.call_method("docs.getUploadServer", params).await
+}
+
+/// Calls `docs.save` for the `file` returned by the upload server.
+///
+/// An audio message or a graffiti is returned as a document with the corresponding `preview`.
+pub async fn save(
+    api: &APIClient,
+    file: &str,
+    title: Option<&str>,
+    tags: &[&str],
+) -> Result<Document> {
+    let mut params = Params::new();
+    params.insert("file".into(), file.into());
+    if let Some(title) = title {
+        params.insert("title".into(), title.into());
+    }
+    if !tags.is_empty() {
+        params.insert("tags".into(), tags.join(","));
+    }
+    let saved: SavedDocument = api.call_method("docs.save", params).await?;
+    Ok(saved.into())
+}
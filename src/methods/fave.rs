@@ -1,6 +1,12 @@
+use crate::{
+    api::APIClient,
+    error::Result,
+    objects::{fave::FaveLink, response::ListResponse, Integer},
+    Params, ParamsExt,
+};
+
 api_category!("fave"; methods {
     add_article,
-    add_link,
     add_page,
     add_post,
     add_product,
@@ -23,3 +29,22 @@ api_category!("fave"; methods {
     set_tags,
     track_page_interaction
 });
+
+/// Calls `fave.getLinks`, returning the links in the bookmarks of the current user.
+pub async fn get_links(
+    api: &APIClient,
+    offset: Integer,
+    count: Integer,
+) -> Result<ListResponse<FaveLink>> {
+    let mut params = Params::new();
+    params.insert_num("offset", offset);
+    params.insert_num("count", count);
+    api.call_method("fave.getLinks", params).await
+}
+
+/// Calls `fave.addLink`, adding the `link` to the bookmarks of the current user.
+pub async fn add_link(api: &APIClient, link: &str) -> Result<Integer> {
+    let mut params = Params::new();
+    params.insert("link".into(), link.into());
+    api.call_method("fave.addLink", params).await
+}
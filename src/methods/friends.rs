@@ -1,3 +1,10 @@
+use crate::{
+    api::APIClient,
+    error::Result,
+    objects::{friend::Request, response::ListResponse, Integer},
+    Params, ParamsExt,
+};
+
 api_category!("friends"; methods {
     add,
     add_list,
@@ -14,7 +21,25 @@ api_category!("friends"; methods {
     get_mutual,
     get_online,
     get_recent,
-    get_requests,
     get_suggestions,
     search
 });
+
+/// Calls `friends.getRequests` with `extended=1`: the incoming requests, or the outgoing ones if `out`.
+///
+/// The mutual friends are included if `need_mutual`.
+pub async fn get_requests(
+    api: &APIClient,
+    out: bool,
+    need_mutual: bool,
+    offset: Integer,
+    count: Integer,
+) -> Result<ListResponse<Request>> {
+    let mut params = Params::new();
+    params.insert_bool("extended", true);
+    params.insert_bool("out", out);
+    params.insert_bool("need_mutual", need_mutual);
+    params.insert_num("offset", offset);
+    params.insert_num("count", count);
+    api.call_method("friends.getRequests", params).await
+}
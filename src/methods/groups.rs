@@ -1,3 +1,18 @@
+use super::csv;
+use crate::{
+    api::{decode, APIClient},
+    error::{Error, Result},
+    objects::{
+        group::{Group, MembershipInfo},
+        response::ListResponse,
+        user::User,
+        Integer,
+    },
+    Params, ParamsExt,
+};
+use serde_json::Value;
+use std::fmt;
+
 api_category!("groups"; methods {
     add_address,
     add_callback_server,
@@ -16,9 +31,7 @@ api_category!("groups"; methods {
     edit_manager,
     edit_place,
     enable_online,
-    get,
     get_banned,
-    get_by_id,
     get_callback_confirmation_code,
     get_callback_servers,
     get_callback_settings,
@@ -28,13 +41,11 @@ api_category!("groups"; methods {
     get_invites,
     get_long_poll_server,
     get_long_poll_settings,
-    get_members,
     get_online_status,
     get_requests,
     get_settings,
     get_token_permissions,
     invite,
-    is_member,
     join,
     leave,
     remove_user,
@@ -44,3 +55,288 @@ api_category!("groups"; methods {
     set_long_poll_settings,
     unban
 });
+
+/// Calls `groups.getById` for the given `group_ids`, requesting the extra `fields`
+/// (e.g. `members_count`, `description`, `activity`, `status`, `contacts`).
+pub async fn get_by_id(
+    api: &APIClient,
+    group_ids: &[Integer],
+    fields: &[&str],
+) -> Result<Vec<Group>> {
+    api.call_method("groups.getById", get_by_id_params(group_ids, fields))
+        .await
+}
+
+/// The `sort` parameter of `groups.getMembers`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemberSort {
+    IdAsc,
+    IdDesc,
+    TimeAsc,
+    TimeDesc,
+}
+
+impl fmt::Display for MemberSort {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            MemberSort::IdAsc => "id_asc",
+            MemberSort::IdDesc => "id_desc",
+            MemberSort::TimeAsc => "time_asc",
+            MemberSort::TimeDesc => "time_desc",
+        })
+    }
+}
+
+/// Calls `groups.getMembers` for the group `group_id`, returning the ids of the members.
+///
+/// `filter` is one of `friends`, `unsure`, `managers`, `donut`, or `None` for all the members.
+pub async fn get_members_ids(
+    api: &APIClient,
+    group_id: Integer,
+    sort: MemberSort,
+    filter: Option<&str>,
+    offset: Integer,
+    count: Integer,
+) -> Result<ListResponse<Integer>> {
+    let params = get_members_params(group_id, &[], sort, filter, offset, count);
+    api.call_method("groups.getMembers", params).await
+}
+
+/// Calls `groups.getMembers` for the group `group_id`, returning the members
+/// as users with the `fields`.
+///
+/// `filter` is one of `friends`, `unsure`, `managers`, `donut`, or `None` for all the members.
+///
+/// Without `fields` VK responds with just the ids, so empty `fields` are rejected
+/// with `Error::Other`; use [`get_members_ids`](fn.get_members_ids.html) for the ids.
+pub async fn get_members(
+    api: &APIClient,
+    group_id: Integer,
+    fields: &[&str],
+    sort: MemberSort,
+    filter: Option<&str>,
+    offset: Integer,
+    count: Integer,
+) -> Result<ListResponse<User>> {
+    if fields.is_empty() {
+        return Err(Error::Other(
+            "groups.getMembers needs fields to respond with users".into(),
+        ));
+    }
+    let params = get_members_params(group_id, fields, sort, filter, offset, count);
+    api.call_method("groups.getMembers", params).await
+}
+
+fn get_members_params(
+    group_id: Integer,
+    fields: &[&str],
+    sort: MemberSort,
+    filter: Option<&str>,
+    offset: Integer,
+    count: Integer,
+) -> Params {
+    let mut params = Params::new();
+    params.insert_num("group_id", group_id);
+    params.insert("sort".into(), sort.to_string());
+    if !fields.is_empty() {
+        params.insert("fields".into(), fields.join(","));
+    }
+    if let Some(filter) = filter {
+        params.insert("filter".into(), filter.into());
+    }
+    params.insert_num("offset", offset);
+    params.insert_num("count", count);
+    params
+}
+
+/// Calls `groups.get` for the communities of the user `user_id`, returning their ids.
+///
+/// `filter` lists e.g. `admin`, `editor`, `moder`, `groups`, `publics`, `events`; all the communities if empty.
+pub async fn get_ids(
+    api: &APIClient,
+    user_id: Integer,
+    filter: &[&str],
+    offset: Integer,
+    count: Integer,
+) -> Result<ListResponse<Integer>> {
+    let params = get_params(user_id, false, &[], filter, offset, count);
+    api.call_method("groups.get", params).await
+}
+
+/// Calls `groups.get` with `extended=1` for the communities of the user `user_id`,
+/// returning them with the `fields`.
+///
+/// `filter` lists e.g. `admin`, `editor`, `moder`, `groups`, `publics`, `events`; all the communities if empty.
+pub async fn get_extended(
+    api: &APIClient,
+    user_id: Integer,
+    fields: &[&str],
+    filter: &[&str],
+    offset: Integer,
+    count: Integer,
+) -> Result<ListResponse<Group>> {
+    let params = get_params(user_id, true, fields, filter, offset, count);
+    api.call_method("groups.get", params).await
+}
+
+fn get_params(
+    user_id: Integer,
+    extended: bool,
+    fields: &[&str],
+    filter: &[&str],
+    offset: Integer,
+    count: Integer,
+) -> Params {
+    let mut params = Params::new();
+    params.insert_num("user_id", user_id);
+    params.insert_bool("extended", extended);
+    if !fields.is_empty() {
+        params.insert("fields".into(), fields.join(","));
+    }
+    if !filter.is_empty() {
+        params.insert("filter".into(), filter.join(","));
+    }
+    params.insert_num("offset", offset);
+    params.insert_num("count", count);
+    params
+}
+
+/// Calls `groups.isMember`, checking whether the user `user_id` is a member of the group `group_id`.
+pub async fn is_member(api: &APIClient, group_id: Integer, user_id: Integer) -> Result<bool> {
+    let mut params = Params::new();
+    params.insert_num("group_id", group_id);
+    params.insert_num("user_id", user_id);
+    let value: Value = api.call_method("groups.isMember", params).await?;
+    is_member_of(value)
+}
+
+/// Calls `groups.isMember` with `extended=1` for the users `user_ids` and the group `group_id`.
+pub async fn are_members(
+    api: &APIClient,
+    group_id: Integer,
+    user_ids: &[Integer],
+) -> Result<Vec<MembershipInfo>> {
+    let mut params = Params::new();
+    params.insert_num("group_id", group_id);
+    params.insert("user_ids".into(), csv(user_ids));
+    params.insert_bool("extended", true);
+    api.call_method("groups.isMember", params).await
+}
+
+// a single user is answered with 1/0 (or true/false), or with an object if extended
+fn is_member_of(value: Value) -> Result<bool> {
+    match value {
+        Value::Bool(member) => Ok(member),
+        Value::Number(n) => Ok(n.as_i64() == Some(1)),
+        value => Ok(decode::<MembershipInfo>("groups.isMember", value)?.member == 1),
+    }
+}
+
+fn get_by_id_params(group_ids: &[Integer], fields: &[&str]) -> Params {
+    let mut params = Params::new();
+    params.insert("group_ids".into(), csv(group_ids));
+    if !fields.is_empty() {
+        params.insert("fields".into(), fields.join(","));
+    }
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn get_by_id_params_with_fields() {
+        let params = get_by_id_params(&[1, 2], &["members_count", "status"]);
+        assert_eq!(params["group_ids"], "1,2");
+        assert_eq!(params["fields"], "members_count,status");
+    }
+
+    #[test]
+    fn get_members_params_sort_and_filter() {
+        let params = get_members_params(1, &["city"], MemberSort::TimeDesc, Some("friends"), 0, 10);
+        assert_eq!(params["sort"], "time_desc");
+        assert_eq!(params["filter"], "friends");
+        assert_eq!(params["fields"], "city");
+    }
+
+    #[test]
+    fn get_params_extended() {
+        let params = get_params(1, true, &["members_count"], &["admin", "editor"], 0, 100);
+        assert_eq!(params["extended"], "1");
+        assert_eq!(params["fields"], "members_count");
+        assert_eq!(params["filter"], "admin,editor");
+        let params = get_params(1, false, &[], &[], 0, 100);
+        assert_eq!(params["extended"], "0");
+        assert!(!params.contains_key("filter"));
+    }
+
+    #[test]
+    fn get_both_shapes() {
+        let ids: ListResponse<Integer> =
+            serde_json::from_value(json!({"count": 2, "items": [1, 2]})).unwrap();
+        assert_eq!(ids.items, vec![1, 2]);
+
+        let groups: ListResponse<Group> = serde_json::from_value(json!({
+            "count": 1,
+            "items": [{
+                "id": 1, "name": "VK API", "screen_name": "apiclub", "is_closed": 0,
+                "type": "group", "photo_50": "", "photo_100": "", "photo_200": "",
+                "members_count": 100
+            }]
+        }))
+        .unwrap();
+        assert_eq!(groups.count, 1);
+        assert_eq!(groups.items[0].screen_name, "apiclub");
+        assert_eq!(groups.items[0].members_count, Some(100));
+    }
+
+    #[test]
+    fn is_member_shapes() {
+        assert!(is_member_of(json!(1)).unwrap());
+        assert!(!is_member_of(json!(0)).unwrap());
+        assert!(is_member_of(json!(true)).unwrap());
+        assert!(is_member_of(json!({"user_id": 1, "member": 1})).unwrap());
+        assert!(is_member_of(json!("yes")).is_err());
+    }
+
+    #[test]
+    fn are_members_response() {
+        let infos: Vec<MembershipInfo> = serde_json::from_value(json!([
+            {"user_id": 1, "member": 1, "can_invite": 0},
+            {"user_id": 2, "member": 0, "request": 1, "invitation": 0, "can_invite": 1}
+        ]))
+        .unwrap();
+        assert_eq!(infos[0].member, 1);
+        assert_eq!(infos[1].user_id, 2);
+        assert_eq!(infos[1].request, Some(1));
+    }
+
+    #[test]
+    fn get_members_both_shapes() {
+        let ids: ListResponse<Integer> =
+            serde_json::from_str(r#"{"count": 2, "items": [1, 2]}"#).unwrap();
+        assert_eq!(ids.items, vec![1, 2]);
+
+        let users: ListResponse<User> = serde_json::from_str(
+            r#"{"count": 1, "items": [{"id": 1, "first_name": "Pavel", "last_name": "Durov", "sex": 2}]}"#,
+        )
+        .unwrap();
+        assert_eq!(users.items[0].sex, Some(2));
+    }
+
+    #[tokio::test]
+    async fn get_members_without_fields() {
+        let api = APIClient::new("token");
+        let result = get_members(&api, 1, &[], MemberSort::IdAsc, None, 0, 10).await;
+        assert!(matches!(result, Err(Error::Other(_))));
+    }
+
+    #[test]
+    fn get_by_id_params_without_fields() {
+        let params = get_by_id_params(&[1], &[]);
+        assert_eq!(params["group_ids"], "1");
+        assert!(!params.contains_key("fields"));
+    }
+}
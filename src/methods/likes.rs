@@ -1,6 +1,90 @@
+use crate::{api::APIClient, error::Result, objects::Integer, Params, ParamsExt};
+use serde_derive::Deserialize;
+use std::fmt;
+
 api_category!("likes"; methods {
-    add,
-    delete,
     get_list,
     is_liked
 });
+
+/// The `type` parameter of the `likes` methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LikeableType {
+    Post,
+    Comment,
+    Photo,
+    Video,
+    Note,
+    Market,
+}
+
+impl fmt::Display for LikeableType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            LikeableType::Post => "post",
+            LikeableType::Comment => "comment",
+            LikeableType::Photo => "photo",
+            LikeableType::Video => "video",
+            LikeableType::Note => "note",
+            LikeableType::Market => "market",
+        })
+    }
+}
+
+/// Calls `likes.add` for the item `item_id` of the `owner_id`, returning the new number of likes.
+pub async fn add(
+    api: &APIClient,
+    type_: LikeableType,
+    owner_id: Integer,
+    item_id: Integer,
+) -> Result<Integer> {
+    let liked: Likes = api
+        .call_method("likes.add", item_params(type_, owner_id, item_id))
+        .await?;
+    Ok(liked.likes)
+}
+
+/// Calls `likes.delete` for the item `item_id` of the `owner_id`, returning the new number of likes.
+pub async fn delete(
+    api: &APIClient,
+    type_: LikeableType,
+    owner_id: Integer,
+    item_id: Integer,
+) -> Result<Integer> {
+    let liked: Likes = api
+        .call_method("likes.delete", item_params(type_, owner_id, item_id))
+        .await?;
+    Ok(liked.likes)
+}
+
+#[derive(Deserialize)]
+struct Likes {
+    likes: Integer,
+}
+
+fn item_params(type_: LikeableType, owner_id: Integer, item_id: Integer) -> Params {
+    let mut params = Params::new();
+    params.insert("type".into(), type_.to_string());
+    params.insert_num("owner_id", owner_id);
+    params.insert_num("item_id", item_id);
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn likes_response() {
+        let liked = serde_json::from_str::<Likes>(r#"{"likes": 12}"#).unwrap();
+        assert_eq!(liked.likes, 12);
+    }
+
+    #[test]
+    fn item_params_of_comment() {
+        let params = item_params(LikeableType::Comment, -1, 2);
+        assert_eq!(params["type"], "comment");
+        assert_eq!(params["owner_id"], "-1");
+        assert_eq!(params["item_id"], "2");
+    }
+}
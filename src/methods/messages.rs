@@ -1,12 +1,23 @@
+use super::csv;
+use crate::{
+    api::APIClient,
+    error::Result,
+    objects::{
+        conversation::{Conversation, PeerId},
+        response::ExtendedResponse,
+        Integer,
+    },
+    Params, ParamsExt,
+};
+use serde_derive::Deserialize;
+use std::collections::HashMap;
+
 api_category!("messages"; methods {
     add_chat_user,
     allow_messages_from_group,
     create_chat,
-    delete,
     delete_chat_photo,
-    delete_conversation,
     deny_messages_from_group,
-    edit,
     edit_chat,
     get_by_conversation_message_id,
     get_by_id,
@@ -14,7 +25,6 @@ api_category!("messages"; methods {
     get_chat_preview,
     get_conversation_members,
     get_conversations,
-    get_conversations_by_id,
     get_history,
     get_history_attachments,
     get_invite_link,
@@ -27,7 +37,6 @@ api_category!("messages"; methods {
     mark_as_answered_conversation,
     mark_as_important,
     mark_as_important_conversation,
-    mark_as_read,
     pin,
     remove_chat_user,
     restore,
@@ -38,3 +47,167 @@ api_category!("messages"; methods {
     set_chat_photo,
     unpin
 });
+
+/// Calls `messages.edit`, replacing the text of the message `message_id` in the `peer_id` with the `message`.
+pub async fn edit(
+    api: &APIClient,
+    peer_id: PeerId,
+    message_id: Integer,
+    message: &str,
+) -> Result<Integer> {
+    api.call_method("messages.edit", edit_params(peer_id, message_id, message))
+        .await
+}
+
+/// Calls `messages.delete` for the `message_ids`, also for the other members of the conversation
+/// if `delete_for_all`, returning `1` (deleted) or `0` for each of the messages by its id.
+pub async fn delete(
+    api: &APIClient,
+    message_ids: &[Integer],
+    delete_for_all: bool,
+) -> Result<HashMap<Integer, Integer>> {
+    api.call_method(
+        "messages.delete",
+        delete_params(message_ids, delete_for_all),
+    )
+    .await
+}
+
+fn edit_params(peer_id: PeerId, message_id: Integer, message: &str) -> Params {
+    let mut params = peer_params(peer_id);
+    params.insert_num("message_id", message_id);
+    params.insert("message".into(), message.into());
+    params
+}
+
+fn delete_params(message_ids: &[Integer], delete_for_all: bool) -> Params {
+    let mut params = Params::new();
+    params.insert("message_ids".into(), csv(message_ids));
+    params.insert_bool("delete_for_all", delete_for_all);
+    params
+}
+
+/// Calls `messages.markAsRead`, marking all the messages of the `peer_id` as read.
+pub async fn mark_as_read(api: &APIClient, peer_id: PeerId) -> Result<Integer> {
+    api.call_method("messages.markAsRead", peer_params(peer_id))
+        .await
+}
+
+/// Calls `messages.deleteConversation`, returning the id of the last deleted message.
+pub async fn delete_conversation(api: &APIClient, peer_id: PeerId) -> Result<Integer> {
+    #[derive(Deserialize)]
+    struct Deleted {
+        last_deleted_id: Integer,
+    }
+
+    let deleted: Deleted = api
+        .call_method("messages.deleteConversation", peer_params(peer_id))
+        .await?;
+    Ok(deleted.last_deleted_id)
+}
+
+/// Calls `messages.getConversationsById` with `extended=1` for the `peer_ids`,
+/// including the `fields` of the users and communities in `profiles` and `groups`.
+pub async fn get_conversations_by_id(
+    api: &APIClient,
+    peer_ids: &[PeerId],
+    fields: &[&str],
+) -> Result<ExtendedResponse<Conversation>> {
+    let mut params = Params::new();
+    params.insert("peer_ids".into(), csv(peer_ids));
+    params.insert_bool("extended", true);
+    if !fields.is_empty() {
+        params.insert("fields".into(), fields.join(","));
+    }
+    api.call_method("messages.getConversationsById", params)
+        .await
+}
+
+fn peer_params(peer_id: PeerId) -> Params {
+    let mut params = Params::new();
+    params.insert_num("peer_id", peer_id);
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockServer;
+    use serde_json::json;
+
+    #[test]
+    fn peer_params_chat() {
+        let params = peer_params(PeerId::chat(5));
+        assert_eq!(params["peer_id"], "2000000005");
+    }
+
+    #[test]
+    fn edit_params_of_chat() {
+        let params = edit_params(PeerId::chat(5), 10, "fixed");
+        assert_eq!(params["peer_id"], "2000000005");
+        assert_eq!(params["message_id"], "10");
+        assert_eq!(params["message"], "fixed");
+    }
+
+    #[test]
+    fn delete_params_for_all() {
+        let params = delete_params(&[1, 2], true);
+        assert_eq!(params["message_ids"], "1,2");
+        assert_eq!(params["delete_for_all"], "1");
+        assert_eq!(delete_params(&[1], false)["delete_for_all"], "0");
+    }
+
+    #[test]
+    fn delete_response() {
+        let deleted: HashMap<Integer, Integer> =
+            serde_json::from_value(json!({"1": 1, "2": 0})).unwrap();
+        assert_eq!(deleted[&1], 1);
+        assert_eq!(deleted[&2], 0);
+    }
+
+    #[test]
+    fn conversations_by_id_response() {
+        let response: ExtendedResponse<Conversation> = serde_json::from_value(json!({
+            "count": 2,
+            "items": [
+                {
+                    "peer": {"id": 1, "type": "user", "local_id": 1},
+                    "in_read": 10, "out_read": 10,
+                    "can_write": {"allowed": true}
+                },
+                {
+                    "peer": {"id": 2000000005, "type": "chat", "local_id": 5},
+                    "in_read": 20, "out_read": 19, "unread_count": 1,
+                    "push_settings": {"disabled_until": 0},
+                    "can_write": {"allowed": false, "reason": 917},
+                    "chat_settings": {"members_count": 3, "title": "rvk", "state": "in", "active_ids": [1, 2]}
+                }
+            ],
+            "profiles": [{"id": 1, "first_name": "Pavel", "last_name": "Durov"}]
+        }))
+        .unwrap();
+        assert_eq!(response.items[1].peer.id, 2000000005);
+        assert_eq!(response.items[1].unread_count, 1);
+        assert_eq!(
+            response.items[1].chat_settings.as_ref().unwrap().title,
+            "rvk"
+        );
+        assert!(response.items[0].chat_settings.is_none());
+        assert_eq!(response.profiles[0].id, 1);
+        assert_eq!(csv(&[PeerId::user(1), PeerId::chat(5)]), "1,2000000005");
+    }
+
+    #[tokio::test]
+    async fn delete_conversation_last_deleted_id() {
+        let server = MockServer::json(json!({"response": {"last_deleted_id": 42}})).await;
+        let api = APIClient::builder("token")
+            .base_url(server.base_url())
+            .build();
+
+        let last = delete_conversation(&api, PeerId::user(1)).await.unwrap();
+        assert_eq!(last, 42);
+        let request = &server.requests()[0];
+        assert_eq!(request.path, "/method/messages.deleteConversation");
+        assert_eq!(request.params["peer_id"], "1");
+    }
+}
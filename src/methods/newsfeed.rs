@@ -1,3 +1,14 @@
+use crate::{
+    api::APIClient,
+    error::{Error, Result},
+    objects::{
+        newsfeed::{Item, NewsFeed, SearchResult},
+        Integer,
+    },
+    Params, ParamsExt,
+};
+use futures_util::stream::{self, Stream, TryStreamExt};
+
 api_category!("newsfeed"; methods {
     add_ban,
     delete_ban,
@@ -11,7 +22,46 @@ api_category!("newsfeed"; methods {
     get_suggested_sources,
     ignore_item,
     save_list,
-    search,
     unignore_item,
     unsubscribe
 });
+
+/// Calls `newsfeed.search` with `extended=1` for the query `q`,
+/// continuing from the `next_from` of the previous results if `start_from` is given.
+pub async fn search(
+    api: &APIClient,
+    q: &str,
+    start_from: Option<&str>,
+    count: Integer,
+) -> Result<SearchResult> {
+    let mut params = Params::new();
+    params.insert("q".into(), q.into());
+    params.insert_bool("extended", true);
+    params.insert_num("count", count);
+    if let Some(start_from) = start_from {
+        params.insert("start_from".into(), start_from.into());
+    }
+    api.call_method("newsfeed.search", params).await
+}
+
+/// Streams the items of `newsfeed.get` with the `params`, following `next_from` across the pages.
+pub fn get_stream(api: &APIClient, params: Params) -> impl Stream<Item = Result<Item>> + '_ {
+    stream::try_unfold(Some(None), move |start_from: Option<Option<String>>| {
+        let mut params = params.clone();
+        async move {
+            let start_from = match start_from {
+                Some(start_from) => start_from,
+                None => return Ok::<_, Error>(None),
+            };
+            if let Some(start_from) = start_from {
+                params.insert("start_from".into(), start_from);
+            }
+
+            let feed: NewsFeed = api.call_method("newsfeed.get", params).await?;
+            let next = feed.next_from.filter(|next| !next.is_empty()).map(Some);
+            let items = feed.items.unwrap_or_default();
+            Ok(Some((stream::iter(items.into_iter().map(Ok)), next)))
+        }
+    })
+    .try_flatten()
+}
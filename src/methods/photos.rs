@@ -1,8 +1,21 @@
+use crate::{
+    api::{decode, APIClient},
+    error::Result,
+    objects::photo::{Album, Photo},
+    objects::{
+        comment::Comment,
+        response::{ExtendedResponse, ListResponse},
+        Integer,
+    },
+    Params, ParamsExt,
+};
+use serde_derive::Deserialize;
+use serde_json::Value;
+
 api_category!("photos"; methods {
     confirm_tag,
     copy,
     create_album,
-    create_comment,
     delete,
     delete_album,
     delete_comment,
@@ -10,13 +23,10 @@ api_category!("photos"; methods {
     edit_album,
     edit_comment,
     get,
-    get_albums,
     get_albums_count,
     get_all,
     get_all_comments,
-    get_by_id,
     get_chat_upload_server,
-    get_comments,
     get_market_album_upload_server,
     get_market_upload_server,
     get_messages_upload_server,
@@ -47,3 +57,135 @@ api_category!("photos"; methods {
 });
 
 api_method!(move_, "photos.move");
+
+/// Calls `photos.getById` for the photos given as `(owner_id, photo_id, access_key)` triples.
+pub async fn get_by_id(
+    api: &APIClient,
+    refs: &[(Integer, Integer, Option<&str>)],
+) -> Result<Vec<Photo>> {
+    let mut params = Params::new();
+    params.insert("photos".into(), photos_param(refs));
+    api.call_method("photos.getById", params).await
+}
+
+/// Calls `photos.getAlbums` with `need_covers=1` for the albums of the `owner_id`,
+/// including the system ones (e.g. the profile photos) if `need_system`.
+pub async fn get_albums(
+    api: &APIClient,
+    owner_id: Integer,
+    need_system: bool,
+    offset: Integer,
+    count: Integer,
+) -> Result<ListResponse<Album>> {
+    let mut params = Params::new();
+    params.insert_num("owner_id", owner_id);
+    params.insert_bool("need_system", need_system);
+    params.insert_bool("need_covers", true);
+    params.insert_num("offset", offset);
+    params.insert_num("count", count);
+    api.call_method("photos.getAlbums", params).await
+}
+
+/// Calls `photos.getComments` with `extended=1` and `need_likes=1` for the photo `photo_id` of the `owner_id`.
+pub async fn get_comments(
+    api: &APIClient,
+    owner_id: Integer,
+    photo_id: Integer,
+    offset: Integer,
+    count: Integer,
+) -> Result<ExtendedResponse<Comment>> {
+    let mut params = photo_params(owner_id, photo_id);
+    params.insert_bool("extended", true);
+    params.insert_bool("need_likes", true);
+    params.insert_num("offset", offset);
+    params.insert_num("count", count);
+    api.call_method("photos.getComments", params).await
+}
+
+/// Calls `photos.createComment` on the photo `photo_id` of the `owner_id`, returning the new comment id.
+pub async fn create_comment(
+    api: &APIClient,
+    owner_id: Integer,
+    photo_id: Integer,
+    message: &str,
+) -> Result<Integer> {
+    let mut params = photo_params(owner_id, photo_id);
+    params.insert("message".into(), message.into());
+    let value: Value = api.call_method("photos.createComment", params).await?;
+    created_comment_id(value)
+}
+
+// documented as the id, but `{comment_id}` as in `wall.createComment` is accepted too
+fn created_comment_id(value: Value) -> Result<Integer> {
+    #[derive(Deserialize)]
+    struct CreatedComment {
+        comment_id: Integer,
+    }
+
+    match value.as_i64() {
+        Some(comment_id) => Ok(comment_id),
+        None => Ok(decode::<CreatedComment>("photos.createComment", value)?.comment_id),
+    }
+}
+
+fn photo_params(owner_id: Integer, photo_id: Integer) -> Params {
+    let mut params = Params::new();
+    params.insert_num("owner_id", owner_id);
+    params.insert_num("photo_id", photo_id);
+    params
+}
+
+/// Builds the `photos` parameter: a comma-separated list of `{owner_id}_{photo_id}`,
+/// followed by `_{access_key}` if there is one.
+fn photos_param(refs: &[(Integer, Integer, Option<&str>)]) -> String {
+    refs.iter()
+        .map(|(owner_id, photo_id, access_key)| match access_key {
+            Some(key) => format!("{}_{}_{}", owner_id, photo_id, key),
+            None => format!("{}_{}", owner_id, photo_id),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn create_comment_response() {
+        assert_eq!(created_comment_id(json!(11)).unwrap(), 11);
+        assert_eq!(created_comment_id(json!({"comment_id": 12})).unwrap(), 12);
+        assert!(created_comment_id(json!("11a")).is_err());
+    }
+
+    #[test]
+    fn get_comments_response() {
+        let json = r#"
+        {
+            "count": 1,
+            "items": [
+                {
+                    "id": 3, "from_id": 5, "date": 1600000000, "text": "nice photo",
+                    "likes": {"count": 2, "user_likes": 1, "can_like": 1}
+                }
+            ],
+            "profiles": [{"id": 5, "first_name": "Ivan", "last_name": "Ivanov"}],
+            "groups": []
+        }
+        "#;
+        let comments = serde_json::from_str::<ExtendedResponse<Comment>>(json).unwrap();
+        assert_eq!(comments.count, Some(1));
+        assert_eq!(comments.items[0].text, "nice photo");
+        assert_eq!(comments.items[0].likes.as_ref().unwrap().count, 2);
+        assert_eq!(comments.profiles[0].id, 5);
+    }
+
+    #[test]
+    fn get_by_id_photos_param() {
+        assert_eq!(
+            photos_param(&[(1, 2, Some("a1b2c3")), (-3, 4, None)]),
+            "1_2_a1b2c3,-3_4"
+        );
+    }
+}
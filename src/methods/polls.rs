@@ -1,8 +1,85 @@
+use super::csv;
+use crate::{
+    api::APIClient, error::Result, objects::poll::Poll, objects::Integer, Params, ParamsExt,
+};
+
 api_category!("polls"; methods {
-    add_vote,
     create,
     delete_vote,
     edit,
-    get_by_id,
     get_voters
 });
+
+/// Calls `polls.getById` for the poll `poll_id` of the `owner_id`.
+pub async fn get_by_id(api: &APIClient, owner_id: Integer, poll_id: Integer) -> Result<Poll> {
+    api.call_method("polls.getById", poll_params(owner_id, poll_id))
+        .await
+}
+
+/// Calls `polls.addVote`, voting for the `answer_ids` in the poll `poll_id` of the `owner_id`.
+pub async fn add_vote(
+    api: &APIClient,
+    owner_id: Integer,
+    poll_id: Integer,
+    answer_ids: &[Integer],
+) -> Result<Integer> {
+    api.call_method(
+        "polls.addVote",
+        add_vote_params(owner_id, poll_id, answer_ids),
+    )
+    .await
+}
+
+fn poll_params(owner_id: Integer, poll_id: Integer) -> Params {
+    let mut params = Params::new();
+    params.insert_num("owner_id", owner_id);
+    params.insert_num("poll_id", poll_id);
+    params
+}
+
+fn add_vote_params(owner_id: Integer, poll_id: Integer, answer_ids: &[Integer]) -> Params {
+    let mut params = poll_params(owner_id, poll_id);
+    params.insert("answer_ids".into(), csv(answer_ids));
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_vote_params_answer_ids() {
+        let params = add_vote_params(-1, 2, &[3, 4]);
+        assert_eq!(params["owner_id"], "-1");
+        assert_eq!(params["poll_id"], "2");
+        assert_eq!(params["answer_ids"], "3,4");
+    }
+
+    #[test]
+    fn get_by_id_response() {
+        let json = r#"
+        {
+            "id": 2,
+            "owner_id": -1,
+            "created": 1600000000,
+            "question": "Tabs or spaces?",
+            "votes": 10,
+            "answers": [
+                {"id": 3, "text": "tabs", "votes": 4, "rate": 40.0},
+                {"id": 4, "text": "spaces", "votes": 6, "rate": 60.0}
+            ],
+            "anonymous": false,
+            "multiple": true,
+            "answer_ids": [4],
+            "end_date": 0,
+            "closed": false,
+            "can_vote": true,
+            "author_id": 1
+        }
+        "#;
+        let poll = serde_json::from_str::<Poll>(json).unwrap();
+        assert_eq!(poll.answers.len(), 2);
+        assert_eq!(poll.answer_ids, Some(vec![4]));
+        assert!(poll.multiple);
+    }
+}
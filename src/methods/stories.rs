@@ -1,3 +1,12 @@
+use super::list_stream;
+use crate::{
+    api::APIClient,
+    error::Result,
+    objects::{user::User, Integer},
+    Params, ParamsExt,
+};
+use futures_util::Stream;
+
 api_category!("stories"; methods {
     ban_owner,
     delete,
@@ -13,3 +22,56 @@ api_category!("stories"; methods {
     hide_reply,
     unban_owner
 });
+
+/// The number of the viewers requested per page (the maximum allowed by `stories.getViewers`).
+pub const VIEWERS_PAGE_SIZE: Integer = 100;
+
+/// Streams all the viewers of the story `story_id` of the `owner_id`
+/// (via `stories.getViewers` with `extended=1`).
+pub fn viewers_stream(
+    api: &APIClient,
+    owner_id: Integer,
+    story_id: Integer,
+) -> impl Stream<Item = Result<User>> + '_ {
+    let mut params = Params::new();
+    params.insert_num("owner_id", owner_id);
+    params.insert_num("story_id", story_id);
+    params.insert_bool("extended", true);
+    list_stream(api, "stories.getViewers", params, VIEWERS_PAGE_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockServer, Response};
+    use futures_util::TryStreamExt;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn viewers_two_pages() {
+        let server = MockServer::start(|request| {
+            let ids = match request.params["offset"].as_str() {
+                "0" => 1..=VIEWERS_PAGE_SIZE,
+                _ => VIEWERS_PAGE_SIZE + 1..=VIEWERS_PAGE_SIZE + 1,
+            };
+            let items: Vec<_> = ids
+                .map(|id| json!({"id": id, "first_name": "Viewer", "last_name": id.to_string()}))
+                .collect();
+            Response::json(json!({"response": {"count": VIEWERS_PAGE_SIZE + 1, "items": items}}))
+        })
+        .await;
+        let api = APIClient::builder("token")
+            .base_url(server.base_url())
+            .build();
+
+        let viewers: Vec<User> = viewers_stream(&api, 1, 2).try_collect().await.unwrap();
+        assert_eq!(viewers.len() as Integer, VIEWERS_PAGE_SIZE + 1);
+        assert_eq!(viewers.last().unwrap().id, VIEWERS_PAGE_SIZE + 1);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].params["offset"], VIEWERS_PAGE_SIZE.to_string());
+        assert_eq!(requests[1].params["story_id"], "2");
+        assert_eq!(requests[1].params["extended"], "1");
+    }
+}
@@ -1,9 +1,67 @@
+use crate::{
+    api::{decode, APIClient},
+    error::Result,
+    objects::{link::LinkStats, screen_name::ResolvedObject},
+    Params, ParamsExt,
+};
+use serde_json::Value;
+
 api_category!("utils"; methods {
     check_link,
     delete_from_last_shortened,
     get_last_shortened_links,
-    get_link_stats,
     get_server_time,
-    get_short_link,
-    resolve_screen_name
+    get_short_link
 });
+
+/// Calls `utils.getLinkStats` for the shortened link `key` (the part after `vk.cc/`),
+/// including the audience breakdown when `extended`.
+pub async fn get_link_stats(api: &APIClient, key: &str, extended: bool) -> Result<LinkStats> {
+    let mut params = Params::new();
+    params.insert("key".into(), key.into());
+    params.insert_bool("extended", extended);
+    api.call_method("utils.getLinkStats", params).await
+}
+
+/// Calls `utils.resolveScreenName` for the `screen_name` (e.g. `durov` or `apiclub`),
+/// returning `None` if nothing has this name.
+pub async fn resolve_screen_name(
+    api: &APIClient,
+    screen_name: &str,
+) -> Result<Option<ResolvedObject>> {
+    let mut params = Params::new();
+    params.insert("screen_name".into(), screen_name.into());
+    let value: Value = api.call_method("utils.resolveScreenName", params).await?;
+    resolved(value)
+}
+
+// VK responds with an empty array for an unknown name
+fn resolved(value: Value) -> Result<Option<ResolvedObject>> {
+    match value {
+        Value::Array(ref items) if items.is_empty() => Ok(None),
+        value => decode("utils.resolveScreenName", value).map(Some),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockServer;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn resolve_user() {
+        let server = MockServer::json(json!({"response": {"type": "user", "object_id": 1}})).await;
+        let api = APIClient::builder("token")
+            .base_url(server.base_url())
+            .build();
+        let resolved = resolve_screen_name(&api, "durov").await.unwrap();
+        assert!(resolved == Some(ResolvedObject::User(1)));
+        assert_eq!(server.requests()[0].params["screen_name"], "durov");
+    }
+
+    #[test]
+    fn resolve_unknown_name() {
+        assert!(resolved(json!([])).unwrap().is_none());
+    }
+}
@@ -1,16 +1,23 @@
+use super::csv;
+use crate::{
+    api::APIClient,
+    error::Result,
+    objects::{
+        attachment::AttachmentRef, comment::Comment, post::Post, response::ExtendedResponse,
+        Integer,
+    },
+    Params, ParamsExt,
+};
+use serde_derive::Deserialize;
+
 api_category!("wall"; methods {
-    create_comment,
     delete,
     delete_comment,
     edit,
     edit_ads_stealth,
     edit_comment,
     get,
-    get_by_id,
-    get_comments,
-    get_reposts,
     pin,
-    post,
     post_ads_stealth,
     report_comment,
     report_post,
@@ -20,3 +27,193 @@ api_category!("wall"; methods {
     search,
     unpin
 });
+
+/// Calls `wall.getById` for the posts given as `(owner_id, post_id)` pairs.
+pub async fn get_by_id(api: &APIClient, refs: &[(Integer, Integer)]) -> Result<Vec<Post>> {
+    let mut params = Params::new();
+    params.insert("posts".into(), posts_param(refs));
+    api.call_method("wall.getById", params).await
+}
+
+/// Calls `wall.getComments` with `extended=1` and `need_likes=1` for the post `post_id` of the `owner_id`.
+pub async fn get_comments(
+    api: &APIClient,
+    owner_id: Integer,
+    post_id: Integer,
+    offset: Integer,
+    count: Integer,
+) -> Result<ExtendedResponse<Comment>> {
+    let mut params = post_params(owner_id, post_id);
+    params.insert_bool("extended", true);
+    params.insert_bool("need_likes", true);
+    params.insert_num("offset", offset);
+    params.insert_num("count", count);
+    api.call_method("wall.getComments", params).await
+}
+
+/// Calls `wall.createComment` on the post `post_id` of the `owner_id`, returning the new comment id.
+pub async fn create_comment(
+    api: &APIClient,
+    owner_id: Integer,
+    post_id: Integer,
+    message: &str,
+) -> Result<Integer> {
+    let mut params = post_params(owner_id, post_id);
+    params.insert("message".into(), message.into());
+    let created: CreatedComment = api.call_method("wall.createComment", params).await?;
+    Ok(created.comment_id)
+}
+
+/// Calls `wall.getReposts` for the post `post_id` of the `owner_id`, returning the reposting posts.
+pub async fn get_reposts(
+    api: &APIClient,
+    owner_id: Integer,
+    post_id: Integer,
+    offset: Integer,
+    count: Integer,
+) -> Result<ExtendedResponse<Post>> {
+    let mut params = post_params(owner_id, post_id);
+    params.insert_num("offset", offset);
+    params.insert_num("count", count);
+    api.call_method("wall.getReposts", params).await
+}
+
+#[derive(Deserialize)]
+struct CreatedComment {
+    comment_id: Integer,
+}
+
+/// Calls `wall.post` with the `params`, returning the new post id.
+///
+/// See [`new_post_params`](fn.new_post_params.html) for the common parameters.
+pub async fn post(api: &APIClient, params: Params) -> Result<Integer> {
+    let created: CreatedPost = api.call_method("wall.post", params).await?;
+    Ok(created.post_id)
+}
+
+/// Calls `wall.post` with just the `message` on the wall of the `owner_id`, returning the new post id.
+pub async fn post_text(api: &APIClient, owner_id: Integer, message: &str) -> Result<Integer> {
+    post(api, new_post_params(owner_id, message, &[])).await
+}
+
+/// Builds the parameters of `wall.post` for a post with the `message` and the `attachments`
+/// on the wall of the `owner_id` (negative for a community).
+pub fn new_post_params(owner_id: Integer, message: &str, attachments: &[AttachmentRef]) -> Params {
+    let mut params = Params::new();
+    params.insert_num("owner_id", owner_id);
+    if !message.is_empty() {
+        params.insert("message".into(), message.into());
+    }
+    if !attachments.is_empty() {
+        params.insert("attachments".into(), csv(attachments));
+    }
+    params
+}
+
+#[derive(Deserialize)]
+struct CreatedPost {
+    post_id: Integer,
+}
+
+fn post_params(owner_id: Integer, post_id: Integer) -> Params {
+    let mut params = Params::new();
+    params.insert_num("owner_id", owner_id);
+    params.insert_num("post_id", post_id);
+    params
+}
+
+/// Builds the `posts` parameter: a comma-separated list of `{owner_id}_{post_id}`.
+fn posts_param(refs: &[(Integer, Integer)]) -> String {
+    refs.iter()
+        .map(|(owner_id, post_id)| format!("{}_{}", owner_id, post_id))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_by_id_posts_param() {
+        assert_eq!(posts_param(&[(1, 2), (-3, 4)]), "1_2,-3_4");
+    }
+
+    #[test]
+    fn create_comment_response() {
+        let created = serde_json::from_str::<CreatedComment>(r#"{"comment_id": 11}"#).unwrap();
+        assert_eq!(created.comment_id, 11);
+    }
+
+    #[test]
+    fn post_response() {
+        let created = serde_json::from_str::<CreatedPost>(r#"{"post_id": 45}"#).unwrap();
+        assert_eq!(created.post_id, 45);
+    }
+
+    #[test]
+    fn new_post_params_with_attachments() {
+        let attachments = [
+            AttachmentRef::new("photo", 1, 2),
+            AttachmentRef::new("doc", 1, 3).with_access_key("abc"),
+        ];
+        let params = new_post_params(-5, "hello", &attachments);
+        assert_eq!(params["owner_id"], "-5");
+        assert_eq!(params["message"], "hello");
+        assert_eq!(params["attachments"], "photo1_2,doc1_3_abc");
+        assert!(!new_post_params(1, "hi", &[]).contains_key("attachments"));
+    }
+
+    #[test]
+    fn get_reposts_response() {
+        let json = r#"
+        {
+            "items": [
+                {
+                    "id": 7, "owner_id": 5, "from_id": 5, "date": 1600000002,
+                    "text": "", "post_type": "post", "marked_as_ads": 0,
+                    "comments": {"count": 0, "can_post": 1},
+                    "likes": {"count": 0, "user_likes": 0, "can_like": 1},
+                    "reposts": {"count": 0, "user_reposted": 0},
+                    "views": {"count": 3}
+                }
+            ],
+            "profiles": [{"id": 5, "first_name": "Ivan", "last_name": "Ivanov"}],
+            "groups": []
+        }
+        "#;
+        let reposts = serde_json::from_str::<ExtendedResponse<Post>>(json).unwrap();
+        assert_eq!(reposts.count, None);
+        assert_eq!(reposts.items[0].id, 7);
+        assert_eq!(reposts.profiles[0].id, 5);
+    }
+
+    #[test]
+    fn get_by_id_response() {
+        let json = r#"
+        [
+            {
+                "id": 2, "owner_id": 1, "from_id": 1, "date": 1600000000,
+                "text": "first", "post_type": "post", "marked_as_ads": 0,
+                "comments": {"count": 0, "can_post": 1},
+                "likes": {"count": 5, "user_likes": 0, "can_like": 1, "can_publish": 1},
+                "reposts": {"count": 1, "user_reposted": 0},
+                "views": {"count": 100}
+            },
+            {
+                "id": 4, "owner_id": -3, "from_id": -3, "date": 1600000001,
+                "text": "second", "post_type": "post", "marked_as_ads": 0,
+                "comments": {"count": 2, "can_post": 1, "groups_can_post": true},
+                "likes": {"count": 0, "user_likes": 0, "can_like": 1, "can_publish": 1},
+                "reposts": {"count": 0, "user_reposted": 0},
+                "views": {"count": 7}
+            }
+        ]
+        "#;
+        let posts = serde_json::from_str::<Vec<Post>>(json).unwrap();
+        assert_eq!(posts.len(), 2);
+        assert_eq!((posts[0].owner_id, posts[0].id), (Some(1), 2));
+        assert_eq!((posts[1].owner_id, posts[1].id), (Some(-3), 4));
+        assert_eq!(posts[1].views.count, 7);
+    }
+}
@@ -0,0 +1,163 @@
+//! A minimal HTTP server for tests: records the requests and answers them with canned responses.
+
+// not every test uses every helper
+#![allow(dead_code)]
+
+use reqwest::Url;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+/// A request received by the `MockServer`.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub params: HashMap<String, String>,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+/// A response sent by the `MockServer`.
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: String,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn json(value: Value) -> Self {
+        Self::bytes("application/json", value.to_string().into_bytes())
+    }
+
+    pub fn bytes(content_type: &str, body: Vec<u8>) -> Self {
+        Self {
+            status: 200,
+            content_type: content_type.into(),
+            body,
+        }
+    }
+}
+
+type Handler = dyn Fn(&Request) -> Response + Send + Sync;
+
+pub struct MockServer {
+    url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+    /// Starts the server, answering every request with the `handler`.
+    pub async fn start(handler: impl Fn(&Request) -> Response + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler: Arc<Handler> = Arc::new(handler);
+
+        let log = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let log = log.clone();
+                let handler = handler.clone();
+                tokio::spawn(async move { serve(stream, &*handler, &log).await });
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    /// Starts the server, answering every request with the same JSON.
+    pub async fn json(value: Value) -> Self {
+        Self::start(move |_| Response::json(value.clone())).await
+    }
+
+    /// The root URL of the server, e.g. `http://127.0.0.1:1234/`.
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    /// The URL to use as the `APIClient` base URL.
+    pub fn base_url(&self) -> String {
+        self.url.clone() + "method/"
+    }
+
+    /// The requests received so far.
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn serve(mut stream: TcpStream, handler: &Handler, log: &Mutex<Vec<Request>>) {
+    let mut data = Vec::new();
+    let mut buf = [0u8; 4096];
+    let header_end = loop {
+        match stream.read(&mut buf).await {
+            Ok(0) | Err(_) => return,
+            Ok(n) => data.extend_from_slice(&buf[..n]),
+        }
+        if let Some(pos) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&data[..header_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split(' ');
+    let method = request_line.next().unwrap_or_default().to_string();
+    let target = request_line.next().unwrap_or_default().to_string();
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()))
+        .collect();
+
+    let length = headers
+        .get("content-length")
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(0);
+    while data.len() < header_end + length {
+        match stream.read(&mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => data.extend_from_slice(&buf[..n]),
+        }
+    }
+    let body = data[header_end..].to_vec();
+
+    let url = Url::parse(&format!("http://localhost{}", target)).unwrap();
+    let mut params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+    if headers.get("content-type").map(String::as_str) == Some("application/x-www-form-urlencoded")
+    {
+        let form = Url::parse(&format!(
+            "http://localhost/?{}",
+            String::from_utf8_lossy(&body)
+        ))
+        .unwrap();
+        params.extend(form.query_pairs().into_owned());
+    }
+
+    let request = Request {
+        method,
+        path: url.path().to_string(),
+        params,
+        headers,
+        body,
+    };
+    let response = handler(&request);
+    log.lock().unwrap().push(request);
+
+    let head = format!(
+        "HTTP/1.1 {} OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    );
+    let _ = stream.write_all(head.as_bytes()).await;
+    let _ = stream.write_all(&response.body).await;
+    let _ = stream.shutdown().await;
+}
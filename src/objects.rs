@@ -14,15 +14,38 @@
 
 use serde_derive::Deserialize;
 
-/// The 'integer' type that is used in objects.
+/// The 'integer' type that is used in objects: `i64`.
+///
+/// Also re-exported at the crate root as `rvk::Integer`.
+///
+/// ```
+/// use rvk::objects::{user::User, Integer};
+///
+/// fn ids(users: &[User]) -> Vec<Integer> {
+///     users.iter().map(|user| user.id).collect()
+/// }
+///
+/// assert!(ids(&[]).is_empty());
+/// let max: rvk::Integer = rvk::objects::Integer::MAX;
+/// assert_eq!(max, i64::MAX);
+/// ```
 pub type Integer = i64;
 
-/// The 'number' type that is used in objects.
+/// The 'number' type that is used in objects: `f64`.
+///
+/// Also re-exported at the crate root as `rvk::Number`.
 pub type Number = f64;
 
-/// The 'boolean' type that is used in objects.
+/// The 'boolean' type that is used in objects: `bool`.
+///
+/// Also re-exported at the crate root as `rvk::Boolean`.
 pub type Boolean = bool;
 
+/// A flag sent by the API as either `0`/`1` or `false`/`true`, depending on the method.
+///
+/// The fields of this type are deserialized with [`ToBool`](invariant_deserialize/struct.ToBool.html).
+pub type VkFlag = Boolean;
+
 pub mod account;
 pub mod app;
 pub mod app_widget;
@@ -33,7 +56,10 @@ pub mod clickable_stickers;
 pub mod comment;
 pub mod comment_board;
 pub mod conversation;
+pub mod database;
 pub mod document;
+pub mod fave;
+pub mod friend;
 pub mod geo;
 pub mod gift;
 pub mod group;
@@ -52,6 +78,8 @@ pub mod post;
 pub mod post_source;
 pub mod privacy;
 pub mod push_settings;
+pub mod response;
+pub mod screen_name;
 pub mod stats;
 pub mod sticker;
 pub mod story;
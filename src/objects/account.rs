@@ -50,3 +50,54 @@ pub struct Account {
     // номер телефона
     pub phone: Option<String>,
 }
+
+/// The counters of the unread/new items: <https://vk.com/dev/account.getCounters>
+#[derive(Deserialize, Clone, Default)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct Counters {
+    // новые заявки в друзья
+    pub friends: Option<Integer>,
+    // предлагаемые друзья
+    pub friends_suggestions: Option<Integer>,
+    // рекомендуемые друзья
+    pub friends_recommendations: Option<Integer>,
+    // непрочитанные сообщения
+    pub messages: Option<Integer>,
+    // новые отметки на фотографиях
+    pub photos: Option<Integer>,
+    // новые отметки на видеозаписях
+    pub videos: Option<Integer>,
+    // новые заметки
+    pub notes: Option<Integer>,
+    // новые подарки
+    pub gifts: Option<Integer>,
+    // приглашения во встречи
+    pub events: Option<Integer>,
+    // приглашения в сообщества
+    pub groups: Option<Integer>,
+    // новые ответы
+    pub notifications: Option<Integer>,
+    // запросы в мобильных играх
+    pub sdk: Option<Integer>,
+    // уведомления от приложений
+    pub app_requests: Option<Integer>,
+    // новые воспоминания
+    pub memories: Option<Integer>,
+    // закладки
+    pub faves: Option<Integer>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counters() {
+        let counters =
+            serde_json::from_str::<Counters>(r#"{"friends": 2, "messages": 15, "unknown": 1}"#)
+                .unwrap();
+        assert_eq!(counters.friends, Some(2));
+        assert_eq!(counters.messages, Some(15));
+        assert_eq!(counters.notifications, None);
+    }
+}
@@ -1,7 +1,8 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/app>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct App {
     // Main fields
     pub id: Integer,
@@ -2,7 +2,8 @@
 
 use super::*;
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct CoverList {
     pub title: String,
     pub title_url: Option<String>,
@@ -12,7 +13,8 @@ pub struct CoverList {
     pub more_url: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Element {
     pub title: String,
     pub button: Option<String>,
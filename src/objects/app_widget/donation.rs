@@ -2,7 +2,8 @@
 
 use super::*;
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Donation {
     pub title: String,
     pub title_url: Option<String>,
@@ -19,7 +20,8 @@ pub struct Donation {
     pub more_url: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Date {
     pub start: Integer,
     pub end: Integer,
@@ -4,7 +4,8 @@
 
 use super::*;
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct List {
     pub title: String,
     pub title_url: Option<String>,
@@ -14,7 +15,8 @@ pub struct List {
     pub more_url: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Element {
     pub title: String,
     pub title_url: Option<String>,
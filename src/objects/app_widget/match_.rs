@@ -2,7 +2,8 @@
 
 use super::*;
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Match {
     pub title: String,
     pub title_url: Option<String>,
@@ -15,7 +16,8 @@ pub struct Match {
     pub more_url: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct MatchInformation {
     pub state: String,
 
@@ -31,26 +33,30 @@ pub struct MatchInformation {
     pub events: Events,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct TeamInformation {
     pub name: String,
     pub descr: String,
     pub icon_id: Option<String>,
 }
 
-#[derive(Deserialize, Copy, Clone, Debug)]
+#[derive(Deserialize, Copy, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Score {
     pub team_a: Integer,
     pub team_b: Integer,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Events {
     pub team_a: Vec<Event>,
     pub team_b: Vec<Event>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Event {
     pub event: String,
     pub minute: Integer,
@@ -2,7 +2,8 @@
 
 use super::*;
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Matches {
     pub title: String,
     pub title_url: Option<String>,
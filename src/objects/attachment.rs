@@ -1,7 +1,8 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/attachments_w>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct WallAttachment {
     #[serde(rename = "type")]
     pub type_: String,
@@ -65,7 +66,8 @@ pub struct WallAttachment {
 }
 
 /// For posts created before 2013
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct PostedPhoto {
     pub id: Integer,
     pub owner_id: Integer,
@@ -82,7 +84,8 @@ pub struct PostedPhoto {
 }
 
 /// For posts created before 2013
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Graffiti {
     pub id: Integer,
     pub owner_id: Integer,
@@ -99,7 +102,8 @@ pub struct Graffiti {
 }
 
 /// For posts created before 2013
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct App {
     pub id: Integer,
     pub name: String,
@@ -115,7 +119,8 @@ pub struct App {
     pub access_key: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Card {
     pub card_id: String,
     pub link_url: String,
@@ -134,7 +139,8 @@ pub struct Card {
     pub access_key: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Event {
     pub id: Integer,
     pub time: Integer,
@@ -155,7 +161,8 @@ pub struct Event {
 }
 
 /// <https://vk.com/dev/objects/attachments_m>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct MessageAttachment {
     #[serde(rename = "type")]
     pub type_: String,
@@ -193,3 +200,56 @@ pub struct MessageAttachment {
     // type = gift
     pub gift: Option<gift::Gift>,
 }
+
+/// A reference to an attachment as the methods accept it in `attachments`:
+/// `<type><owner_id>_<media_id>`, followed by `_<access_key>` if there is one (e.g. `photo1_2_abc`)
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct AttachmentRef {
+    pub type_: String,
+    pub owner_id: Integer,
+    pub media_id: Integer,
+    pub access_key: Option<String>,
+}
+
+impl AttachmentRef {
+    pub fn new(type_: impl Into<String>, owner_id: Integer, media_id: Integer) -> Self {
+        Self {
+            type_: type_.into(),
+            owner_id,
+            media_id,
+            access_key: None,
+        }
+    }
+
+    pub fn with_access_key(mut self, access_key: impl Into<String>) -> Self {
+        self.access_key = Some(access_key.into());
+        self
+    }
+}
+
+impl std::fmt::Display for AttachmentRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}{}_{}", self.type_, self.owner_id, self.media_id)?;
+        if let Some(access_key) = &self.access_key {
+            write!(f, "_{}", access_key)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attachment_ref() {
+        assert_eq!(AttachmentRef::new("photo", -1, 2).to_string(), "photo-1_2");
+        assert_eq!(
+            AttachmentRef::new("doc", 1, 2)
+                .with_access_key("abc")
+                .to_string(),
+            "doc1_2_abc"
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/audio>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Audio {
     pub id: Integer,
     pub owner_id: Integer,
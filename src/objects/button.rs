@@ -1,13 +1,15 @@
 use super::*;
 
 /// <https://vk.com/dev/link_button>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Button {
     pub title: String,
     pub action: ButtonAction,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct ButtonAction {
     #[serde(rename = "type")]
     pub type_: String,
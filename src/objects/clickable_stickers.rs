@@ -1,14 +1,16 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/clickable_stickers>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct ClickableStickers {
     pub original_width: Integer,
     pub original_height: Integer,
     pub clickable_stickers: Vec<ClickableSticker>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct ClickableSticker {
     #[serde(rename = "type")]
     pub type_: String,
@@ -18,7 +20,8 @@ pub struct ClickableSticker {
     pub hashtag: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Point2D {
     pub x: Integer,
     pub y: Integer,
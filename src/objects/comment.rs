@@ -1,7 +1,8 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/comment>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Comment {
     pub id: Integer,
     pub from_id: Integer,
@@ -10,6 +11,8 @@ pub struct Comment {
     pub reply_to_user: Option<Integer>,
     pub reply_to_comment: Option<Integer>,
     pub attachments: Option<Vec<attachment::WallAttachment>>,
+    // with `need_likes=1`
+    pub likes: Option<post::Likes>,
     pub parents_stack: Option<Vec<Integer>>,
     pub thread: Option<Thread>,
     pub deleted: Option<Boolean>,
@@ -19,7 +22,8 @@ pub struct Comment {
     pub owner_id: Option<Integer>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Thread {
     pub count: Integer,
     pub items: Option<Vec<Comment>>,
@@ -27,3 +31,43 @@ pub struct Thread {
     pub show_reply_button: Boolean,
     pub groups_can_post: Boolean,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::response::ExtendedResponse;
+
+    #[test]
+    fn extended_comments() {
+        let json = r#"
+        {
+            "count": 1,
+            "items": [
+                {
+                    "id": 10,
+                    "from_id": 1,
+                    "date": 1600000000,
+                    "text": "nice",
+                    "reply_to_user": 2,
+                    "reply_to_comment": 9,
+                    "likes": {"count": 3, "user_likes": 0, "can_like": 1},
+                    "attachments": [
+                        {
+                            "type": "link",
+                            "link": {"url": "https://vk.com", "title": "VK"}
+                        }
+                    ]
+                }
+            ],
+            "profiles": [{"id": 1, "first_name": "Pavel", "last_name": "Durov"}],
+            "groups": []
+        }
+        "#;
+        let comments = serde_json::from_str::<ExtendedResponse<Comment>>(json).unwrap();
+        let comment = &comments.items[0];
+        assert_eq!(comment.reply_to_comment, Some(9));
+        assert_eq!(comment.likes.as_ref().unwrap().count, 3);
+        assert_eq!(comment.attachments.as_ref().unwrap()[0].type_, "link");
+        assert_eq!(comments.profiles[0].id, 1);
+    }
+}
@@ -1,7 +1,8 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/comment_board>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct BoardComment {
     pub id: Integer,
     pub from_id: Integer,
@@ -11,7 +12,8 @@ pub struct BoardComment {
     pub likes: Option<Likes>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Likes {
     pub count: Integer,
     pub user_likes: Integer,
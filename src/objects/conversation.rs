@@ -1,49 +1,115 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/conversation>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Conversation {
     pub peer: Peer,
     pub in_read: Integer,
     pub out_read: Integer,
+    // not present if there are no unread messages
+    #[serde(default)]
     pub unread_count: Integer,
+    #[serde(default)]
     pub important: Boolean,
+    #[serde(default)]
     pub unanswered: Boolean,
-    pub push_settings: PushSettings,
+    pub push_settings: Option<PushSettings>,
     pub can_write: WritePermission,
-    pub chat_settings: ChatSettings,
+    // only for the chats
+    pub chat_settings: Option<ChatSettings>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Peer {
     pub id: Integer,
 
+    // user, chat, group or email
     #[serde(rename = "type")]
-    pub type_: Integer,
+    pub type_: String,
 
     pub local_id: Integer,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+/// A `peer_id` in the messages: a user, a chat or a community
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct PeerId(pub Integer);
+
+impl PeerId {
+    /// The offset of the chat `peer_id`s from the chat ids
+    pub const CHAT_OFFSET: Integer = 2_000_000_000;
+
+    pub fn user(user_id: Integer) -> Self {
+        Self(user_id)
+    }
+
+    pub fn chat(chat_id: Integer) -> Self {
+        Self(Self::CHAT_OFFSET + chat_id)
+    }
+
+    /// `group_id` is positive, like in `Group::id`
+    pub fn group(group_id: Integer) -> Self {
+        Self(-group_id)
+    }
+
+    pub fn is_chat(self) -> bool {
+        self.0 > Self::CHAT_OFFSET
+    }
+}
+
+impl From<Integer> for PeerId {
+    fn from(peer_id: Integer) -> Self {
+        Self(peer_id)
+    }
+}
+
+impl std::fmt::Display for PeerId {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct PushSettings {
     pub disabled_until: Integer,
     pub disabled_forever: Option<Boolean>,
     pub no_sound: Option<Boolean>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct WritePermission {
     pub allowed: Boolean,
     pub reason: Option<Integer>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct ChatSettings {
     pub members_count: Integer,
     pub title: String,
     pub pinned_message: Option<message::Pinned>,
     pub state: String,
-    pub photo: message::Photo,
+    pub photo: Option<message::Photo>,
+    #[serde(default)]
     pub active_ids: Vec<Integer>,
+    #[serde(default)]
     pub is_group_channel: Boolean,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peer_ids() {
+        assert_eq!(PeerId::user(1).to_string(), "1");
+        assert_eq!(PeerId::chat(1).to_string(), "2000000001");
+        assert_eq!(PeerId::group(1).to_string(), "-1");
+        assert!(PeerId::chat(1).is_chat());
+        assert!(!PeerId::user(1).is_chat());
+    }
+}
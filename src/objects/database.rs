@@ -0,0 +1,32 @@
+use super::*;
+
+/// An item of the `database` lists: chair, faculty, school, city, etc.
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct NamedObject {
+    pub id: Integer,
+    pub title: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::response::ListResponse;
+
+    #[test]
+    fn named_objects_list() {
+        let json = r#"
+        {
+            "count": 2,
+            "items": [
+                {"id": 1, "title": "School #1"},
+                {"id": 2, "title": "School #2", "extra": "ignored"}
+            ]
+        }
+        "#;
+        let list = serde_json::from_str::<ListResponse<NamedObject>>(json).unwrap();
+        assert_eq!(list.count, 2);
+        assert_eq!(list.items[1].id, 2);
+        assert_eq!(list.items[1].title, "School #2");
+    }
+}
@@ -1,14 +1,23 @@
+use super::invariant_deserialize::null_as_default;
 use super::*;
+use crate::{api::APIClient, error::Result};
 
 /// <https://vk.com/dev/objects/doc>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Document {
     pub id: Integer,
     pub owner_id: Integer,
+    // may be null, e.g. for documents with restricted access
+    #[serde(default, deserialize_with = "null_as_default")]
     pub title: String,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub size: Integer,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub ext: String,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub url: String,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub date: Integer,
 
     #[serde(rename = "type")]
@@ -25,29 +34,346 @@ pub struct Document {
     pub access_key: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+impl Document {
+    /// Downloads the document from its `url` with the HTTP client of the `api`.
+    ///
+    /// A mismatch between the received size and `size` (if known) is logged as a warning.
+    /// The received size is the `Content-Length` of the response, or the length of the body without one.
+    pub async fn download(&self, api: &APIClient) -> Result<Vec<u8>> {
+        let response = api.fetch(&self.url).await?;
+        let content_length = response.content_length();
+        let mut mismatch = content_length.and_then(|length| self.check_size(length));
+        let bytes = response.bytes().await?;
+        if content_length.is_none() {
+            mismatch = self.check_size(bytes.len() as u64);
+        }
+        if let Some((expected, actual)) = mismatch {
+            log::warn!(
+                "document {}_{} is {} bytes, expected {}",
+                self.owner_id,
+                self.id,
+                actual,
+                expected
+            );
+        }
+        Ok(bytes.to_vec())
+    }
+
+    /// Returns the expected and the actual size if `length` does not match `size` (if known).
+    fn check_size(&self, length: u64) -> Option<(u64, u64)> {
+        if self.size > 0 && self.size as u64 != length {
+            Some((self.size as u64, length))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct DocumentPreview {
     pub photo: Option<Photo>,
     pub graffiti: Option<Graffiti>,
     pub audio_msg: Option<AudioMessage>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+impl DocumentPreview {
+    /// Returns the URL to display the document with: the largest preview photo,
+    /// the graffiti image or the mp3 of the audio message, whichever is present.
+    pub fn media_url(&self) -> Option<&str> {
+        let photo = self.photo.as_ref().and_then(|photo| {
+            photo
+                .sizes
+                .iter()
+                .filter(|size| size.url.is_some() || size.src.is_some())
+                .max_by_key(|size| size.width.unwrap_or(0) * size.height.unwrap_or(0))
+                .and_then(|size| size.url.as_deref().or(size.src.as_deref()))
+        });
+        photo
+            .or_else(|| self.graffiti.as_ref().map(|graffiti| graffiti.src.as_str()))
+            .or_else(|| self.audio_msg.as_ref().map(|audio| audio.link_mp3.as_str()))
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Photo {
     pub sizes: Vec<photo::Size>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Graffiti {
+    // `url` in the response of `docs.save`
+    #[serde(alias = "url")]
     pub src: String,
     pub width: Integer,
     pub height: Integer,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct AudioMessage {
     pub duration: Integer,
     pub waveform: Vec<Integer>,
     pub link_ogg: String,
     pub link_mp3: String,
 }
+
+/// The response of `docs.getUploadServer`
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct UploadServer {
+    pub upload_url: String,
+}
+
+/// The response of `docs.save`: `{"type": "doc", "doc": {...}}` and so on.
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SavedDocument {
+    Doc {
+        doc: Document,
+    },
+    AudioMessage {
+        audio_message: SavedAttachment<AudioMessage>,
+    },
+    Graffiti {
+        graffiti: SavedAttachment<Graffiti>,
+    },
+}
+
+/// An audio message or a graffiti saved with `docs.save`.
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct SavedAttachment<T> {
+    pub id: Integer,
+    pub owner_id: Integer,
+    pub access_key: Option<String>,
+    #[serde(flatten)]
+    pub content: T,
+}
+
+impl From<SavedDocument> for Document {
+    /// Represents an audio message (type 5, audio) or a graffiti (type 4, image)
+    /// as a document with the corresponding `preview`.
+    fn from(saved: SavedDocument) -> Self {
+        let (id, owner_id, access_key, type_, url, preview) = match saved {
+            SavedDocument::Doc { doc } => return doc,
+            SavedDocument::AudioMessage { audio_message: a } => (
+                a.id,
+                a.owner_id,
+                a.access_key,
+                5,
+                a.content.link_mp3.clone(),
+                DocumentPreview {
+                    photo: None,
+                    graffiti: None,
+                    audio_msg: Some(a.content),
+                },
+            ),
+            SavedDocument::Graffiti { graffiti: g } => (
+                g.id,
+                g.owner_id,
+                g.access_key,
+                4,
+                g.content.src.clone(),
+                DocumentPreview {
+                    photo: None,
+                    graffiti: Some(g.content),
+                    audio_msg: None,
+                },
+            ),
+        };
+        Document {
+            id,
+            owner_id,
+            title: String::new(),
+            size: 0,
+            ext: String::new(),
+            url,
+            date: 0,
+            type_,
+            preview: Some(preview),
+            access_key,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockServer, Response};
+
+    #[test]
+    fn document_with_nulls() {
+        let json = r#"
+        {
+            "id": 1,
+            "owner_id": 2,
+            "title": "report.pdf",
+            "size": null,
+            "ext": "pdf",
+            "url": null,
+            "date": 1600000000,
+            "type": 1,
+            "preview": null
+        }
+        "#;
+        let doc = serde_json::from_str::<Document>(json).unwrap();
+        assert_eq!(doc.size, 0);
+        assert_eq!(doc.url, "");
+        assert!(doc.preview.is_none());
+    }
+
+    #[test]
+    fn saved_doc() {
+        let json = r#"
+        {
+            "type": "doc",
+            "doc": {
+                "id": 1, "owner_id": 2, "title": "report.pdf", "size": 15, "ext": "pdf",
+                "url": "https://vk.com/doc2_1", "date": 1600000000, "type": 1
+            }
+        }
+        "#;
+        let doc = Document::from(serde_json::from_str::<SavedDocument>(json).unwrap());
+        assert_eq!(doc.title, "report.pdf");
+        assert_eq!(doc.type_, 1);
+    }
+
+    #[test]
+    fn saved_audio_message() {
+        let json = r#"
+        {
+            "type": "audio_message",
+            "audio_message": {
+                "id": 3, "owner_id": 2, "duration": 3, "waveform": [0, 15, 31],
+                "link_ogg": "https://vk.com/a.ogg", "link_mp3": "https://vk.com/a.mp3",
+                "access_key": "abc"
+            }
+        }
+        "#;
+        let doc = Document::from(serde_json::from_str::<SavedDocument>(json).unwrap());
+        assert_eq!(doc.id, 3);
+        assert_eq!(doc.type_, 5);
+        assert_eq!(doc.url, "https://vk.com/a.mp3");
+        assert_eq!(doc.access_key.as_deref(), Some("abc"));
+        let preview = doc.preview.unwrap();
+        assert_eq!(preview.audio_msg.unwrap().duration, 3);
+    }
+
+    #[test]
+    fn saved_graffiti() {
+        let json = r#"
+        {
+            "type": "graffiti",
+            "graffiti": {"id": 4, "owner_id": 2, "url": "https://vk.com/g.png", "width": 720, "height": 720}
+        }
+        "#;
+        let doc = Document::from(serde_json::from_str::<SavedDocument>(json).unwrap());
+        assert_eq!(doc.type_, 4);
+        assert_eq!(
+            doc.preview.unwrap().media_url(),
+            Some("https://vk.com/g.png")
+        );
+    }
+
+    #[test]
+    fn media_url_of_photo() {
+        let preview = serde_json::from_str::<DocumentPreview>(
+            r#"
+            {
+                "photo": {
+                    "sizes": [
+                        {"src": "https://vk.com/s.jpg", "width": 100, "height": 75, "type": "s"},
+                        {"src": "https://vk.com/x.jpg", "width": 604, "height": 453, "type": "x"},
+                        {"src": "https://vk.com/m.jpg", "width": 130, "height": 98, "type": "m"}
+                    ]
+                }
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(preview.media_url(), Some("https://vk.com/x.jpg"));
+    }
+
+    #[test]
+    fn media_url_of_graffiti() {
+        let preview = serde_json::from_str::<DocumentPreview>(
+            r#"{"graffiti": {"src": "https://vk.com/g.png", "width": 720, "height": 720}}"#,
+        )
+        .unwrap();
+        assert_eq!(preview.media_url(), Some("https://vk.com/g.png"));
+    }
+
+    #[test]
+    fn media_url_of_audio_message() {
+        let preview = serde_json::from_str::<DocumentPreview>(
+            r#"
+            {
+                "audio_msg": {
+                    "duration": 3,
+                    "waveform": [0, 15, 31],
+                    "link_ogg": "https://vk.com/a.ogg",
+                    "link_mp3": "https://vk.com/a.mp3"
+                }
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(preview.media_url(), Some("https://vk.com/a.mp3"));
+    }
+
+    #[test]
+    fn media_url_of_nothing() {
+        let preview = serde_json::from_str::<DocumentPreview>("{}").unwrap();
+        assert_eq!(preview.media_url(), None);
+    }
+
+    #[tokio::test]
+    async fn download_document() {
+        let content = b"%PDF-1.4 report".to_vec();
+        let served = content.clone();
+        let server =
+            MockServer::start(move |_| Response::bytes("application/pdf", served.clone())).await;
+        let doc = serde_json::from_value::<Document>(serde_json::json!({
+            "id": 1,
+            "owner_id": 2,
+            "title": "report.pdf",
+            "size": content.len(),
+            "ext": "pdf",
+            "url": server.url() + "doc2_1",
+            "date": 1600000000,
+            "type": 1
+        }))
+        .unwrap();
+
+        let bytes = doc.download(&APIClient::new("token")).await.unwrap();
+        assert_eq!(bytes, content);
+        assert_eq!(bytes.len() as Integer, doc.size);
+        assert_eq!(server.requests()[0].path, "/doc2_1");
+    }
+
+    #[tokio::test]
+    async fn download_document_size_mismatch() {
+        let server =
+            MockServer::start(|_| Response::bytes("application/pdf", b"truncated".to_vec())).await;
+        let doc = serde_json::from_value::<Document>(serde_json::json!({
+            "id": 9,
+            "owner_id": 7,
+            "title": "report.pdf",
+            "size": 100,
+            "ext": "pdf",
+            "url": server.url() + "doc7_9",
+            "date": 1600000000,
+            "type": 1
+        }))
+        .unwrap();
+
+        let bytes = doc.download(&APIClient::new("token")).await.unwrap();
+        assert_eq!(bytes, b"truncated");
+        assert_eq!(doc.check_size(bytes.len() as u64), Some((100, 9)));
+        assert_eq!(doc.check_size(100), None);
+    }
+}
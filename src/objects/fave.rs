@@ -0,0 +1,45 @@
+use super::invariant_deserialize::ToStr;
+use super::*;
+
+/// A link in the bookmarks: <https://vk.com/dev/fave.getLinks>
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct FaveLink {
+    // e.g. "2_-1_https://vk.com/dev"
+    #[serde(deserialize_with = "ToStr::deserialize")]
+    pub id: String,
+    pub url: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub photo: Option<photo::Photo>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::response::ListResponse;
+
+    #[test]
+    fn fave_links() {
+        let json = r#"
+        {
+            "count": 2,
+            "items": [
+                {
+                    "id": "2_0_https://vk.com/dev", "url": "https://vk.com/dev",
+                    "title": "VK for developers", "description": "",
+                    "photo": {"id": 1, "sizes": [{"url": "https://vk.com/m.jpg", "width": 130, "height": 98, "type": "m"}]}
+                },
+                {"id": 5, "url": "https://rust-lang.org"}
+            ]
+        }
+        "#;
+        let links = serde_json::from_str::<ListResponse<FaveLink>>(json).unwrap();
+        assert_eq!(links.items[0].title, "VK for developers");
+        assert!(links.items[0].photo.is_some());
+        assert_eq!(links.items[1].id, "5");
+        assert_eq!(links.items[1].title, "");
+    }
+}
@@ -0,0 +1,43 @@
+use super::*;
+
+/// An item of `friends.getRequests` with `extended=1`
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct Request {
+    pub user_id: Integer,
+    pub message: Option<String>,
+    // with `need_mutual=1`
+    pub mutual: Option<Mutual>,
+}
+
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct Mutual {
+    pub count: Integer,
+    pub users: Vec<Integer>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::response::ListResponse;
+
+    #[test]
+    fn requests_with_mutual() {
+        let json = r#"
+        {
+            "count": 2,
+            "items": [
+                {"user_id": 1, "mutual": {"count": 2, "users": [3, 4]}},
+                {"user_id": 2, "message": "hi"}
+            ]
+        }
+        "#;
+        let requests = serde_json::from_str::<ListResponse<Request>>(json).unwrap();
+        assert_eq!(requests.count, 2);
+        let mutual = requests.items[0].mutual.as_ref().unwrap();
+        assert_eq!(mutual.count, 2);
+        assert_eq!(mutual.users, vec![3, 4]);
+        assert!(requests.items[1].mutual.is_none());
+    }
+}
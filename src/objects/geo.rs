@@ -1,7 +1,8 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/geo>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Geo {
     #[serde(rename = "type")]
     pub type_: String,
@@ -10,25 +11,29 @@ pub struct Geo {
     pub place: Option<Place>,
 }
 
-#[derive(Deserialize, Copy, Clone, Debug)]
+#[derive(Deserialize, Copy, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Coordinates {
     pub latitude: Number,
     pub longitude: Number,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct City {
     pub id: Option<Integer>,
     pub title: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Country {
     pub id: Option<Integer>,
     pub title: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Place {
     pub id: Option<Integer>,
     pub title: Option<String>,
@@ -1,7 +1,8 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/gift>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Gift {
     pub id: Integer,
     pub thumb_256: String,
@@ -1,7 +1,9 @@
+use super::invariant_deserialize::ToBool;
 use super::*;
 
 /// <https://vk.com/dev/objects/group>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Group {
     // Main fields
     pub id: Integer,
@@ -41,33 +43,40 @@ pub struct Group {
     pub description: Option<String>,
     pub fixed_post: Option<Integer>,
     pub has_photo: Option<Integer>,
-    pub is_favorite: Option<Integer>,
-    pub is_hidden_from_feed: Option<Integer>,
+    #[serde(default, deserialize_with = "ToBool::deserialize_opt")]
+    pub is_favorite: Option<VkFlag>,
+    #[serde(default, deserialize_with = "ToBool::deserialize_opt")]
+    pub is_hidden_from_feed: Option<VkFlag>,
     pub is_messages_blocked: Option<Integer>,
     pub links: Option<Vec<Link>>,
     pub main_album_id: Option<Integer>,
     pub main_section: Option<Integer>,
     pub market: Option<Market>,
     pub member_status: Option<Integer>,
+    pub members_count: Option<Integer>,
     pub place: Option<geo::Place>,
     pub public_date_label: Option<String>,
     pub site: Option<String>,
     pub start_date: Option<Integer>,
     pub finish_date: Option<Integer>,
     pub status: Option<String>,
-    pub trending: Option<Integer>,
-    pub verified: Option<Integer>,
+    #[serde(default, deserialize_with = "ToBool::deserialize_opt")]
+    pub trending: Option<VkFlag>,
+    #[serde(default, deserialize_with = "ToBool::deserialize_opt")]
+    pub verified: Option<VkFlag>,
     pub wall: Option<Integer>,
     pub wiki_page: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct BlacklistInfo {
     pub end_date: Integer,
     pub comment: String,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Contact {
     pub user_id: Option<Integer>,
     pub desc: Option<String>,
@@ -75,7 +84,8 @@ pub struct Contact {
     pub email: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Counters {
     pub photos: Option<Integer>,
     pub albums: Option<Integer>,
@@ -85,13 +95,15 @@ pub struct Counters {
     pub docs: Option<Integer>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Cover {
     pub enabled: Integer,
     pub images: Vec<photo::Image>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Link {
     pub id: Integer,
     pub url: String,
@@ -101,7 +113,8 @@ pub struct Link {
     pub photo_100: String,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Market {
     pub enabled: Integer,
     pub price_min: Option<Integer>,
@@ -111,3 +124,69 @@ pub struct Market {
     pub currency: Option<link::Price>,
     pub currency_text: Option<String>,
 }
+
+/// The membership of a user in a community: <https://vk.com/dev/groups.isMember>
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct MembershipInfo {
+    pub user_id: Integer,
+    // 1, если пользователь является участником сообщества
+    pub member: Integer,
+    // 1, если пользователь подал заявку на вступление (при extended=1)
+    pub request: Option<Integer>,
+    // 1, если пользователь приглашён в сообщество (при extended=1)
+    pub invitation: Option<Integer>,
+    // 1, если пользователя можно пригласить в сообщество
+    pub can_invite: Option<Integer>,
+    // 1, если приглашение можно отозвать
+    pub can_recall: Option<Integer>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_with_members_count() {
+        let json = r#"
+        {
+            "id": 1,
+            "name": "VK API",
+            "screen_name": "apiclub",
+            "is_closed": 0,
+            "type": "group",
+            "photo_50": "https://vk.com/images/50.png",
+            "photo_100": "https://vk.com/images/100.png",
+            "photo_200": "https://vk.com/images/200.png",
+            "members_count": 1234567,
+            "activity": "Open group",
+            "status": "up and running",
+            "description": "Official API group",
+            "contacts": [{"user_id": 1, "desc": "admin"}]
+        }
+        "#;
+        let group = serde_json::from_str::<Group>(json).unwrap();
+        assert_eq!(group.members_count, Some(1234567));
+        assert_eq!(group.activity.as_deref(), Some("Open group"));
+        assert_eq!(group.contacts.unwrap()[0].user_id, Some(1));
+    }
+
+    #[test]
+    fn group_flags_as_numbers_and_booleans() {
+        let group = |flags: &str| {
+            let json = format!(
+                r#"{{"id": 1, "name": "VK API", "screen_name": "apiclub", "is_closed": 0, "type": "group",
+                    "photo_50": "", "photo_100": "", "photo_200": "", {}}}"#,
+                flags
+            );
+            serde_json::from_str::<Group>(&json).unwrap()
+        };
+        let numbers = group(r#""verified": 1, "trending": 0"#);
+        assert_eq!(numbers.verified, Some(true));
+        assert_eq!(numbers.trending, Some(false));
+        let booleans = group(r#""verified": true, "trending": false"#);
+        assert_eq!(booleans.verified, Some(true));
+        assert_eq!(booleans.trending, Some(false));
+        assert_eq!(booleans.is_favorite, None);
+    }
+}
@@ -5,12 +5,14 @@
 //! (at least in Feb'2021, later it became an Integer being the braking change).
 //!
 //! Another example, the Link::product::price::amount might be string in some json responses as well as number in others
+//!
+//! And the flags (e.g. User::verified) are `0`/`1` in some responses and `false`/`true` in others
 
 use core::marker::PhantomData;
 use num::cast::FromPrimitive;
 use serde::{
     de::{Error as SerdeError, Unexpected, Visitor},
-    Deserializer,
+    Deserialize, Deserializer,
 };
 use std::{any::type_name, fmt, str::FromStr};
 
@@ -41,6 +43,12 @@ impl<T: FromStr + FromPrimitive> ToNum<T> {
     }
 }
 
+impl<T: FromStr + FromPrimitive> Default for ToNum<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'de, T: FromStr + FromPrimitive> Visitor<'de> for ToNum<T> {
     type Value = T;
 
@@ -57,7 +65,8 @@ impl<'de, T: FromStr + FromPrimitive> Visitor<'de> for ToNum<T> {
     where
         E: SerdeError,
     {
-        if let Ok(v) = s.parse::<T>() {
+        // the surrounding whitespace is occasionally sent too, e.g. " 123"
+        if let Ok(v) = s.trim().parse::<T>() {
             Ok(v)
         } else {
             Err(SerdeError::invalid_value(Unexpected::Str(s), &self))
@@ -138,7 +147,7 @@ mod test_i64 {
 
     #[test]
     fn i64_dont_deserialize_too_large_u64() {
-        let json_item = format!(r#"{{"value": "{}"\}}"#, std::i64::MAX as u64 + 1);
+        let json_item = format!(r#"{{"value": "{}"\}}"#, i64::MAX as u64 + 1);
         let res = serde_json::from_str::<Item>(&json_item);
         assert!(res.is_err());
     }
@@ -187,6 +196,28 @@ mod test_i64 {
         assert!(res.is_err());
     }
 
+    #[test]
+    fn i64_deserialize_string_with_whitespace() {
+        let json = r#"
+        {
+            "value": " 123 "
+        }
+        "#;
+        let item = serde_json::from_str::<Item>(json).unwrap();
+        assert_eq!(item.value, 123);
+    }
+
+    #[test]
+    fn i64_dont_deserialize_string_with_inner_whitespace() {
+        let json_item = r#"
+        {
+            "value": "1 2 3"
+        }
+        "#;
+        let res = serde_json::from_str::<Item>(json_item);
+        assert!(res.is_err());
+    }
+
     #[test]
     fn i64_dont_deserialize_arbitrary_string() {
         let json_item = r#"
@@ -256,7 +287,7 @@ mod test_i16 {
 
     #[test]
     fn i16_dont_deserialize_too_large_u16() {
-        let json_item = format!(r#"{{"value": "{}"\}}"#, std::i16::MAX as u16 + 1);
+        let json_item = format!(r#"{{"value": "{}"\}}"#, i16::MAX as u16 + 1);
         let res = serde_json::from_str::<Item>(&json_item);
         assert!(res.is_err());
     }
@@ -352,6 +383,60 @@ mod test_i64_opt {
     }
 }
 
+/// Deserializes an explicit `null` as the default value of `T`,
+/// to be used together with `#[serde(default)]` covering the missing field
+pub fn null_as_default<'de, D, T>(de: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(de)?.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod test_null_as_default {
+    use super::*;
+
+    #[derive(Deserialize, Debug)]
+    struct Item {
+        #[serde(default, deserialize_with = "null_as_default")]
+        value: i64,
+    }
+
+    #[test]
+    fn null_as_default_value() {
+        let json = r#"
+        {
+            "value": 123
+        }
+        "#;
+        let item = serde_json::from_str::<Item>(json).unwrap();
+        assert_eq!(item.value, 123);
+    }
+
+    #[test]
+    fn null_as_default_null() {
+        let json = r#"
+        {
+            "value": null
+        }
+        "#;
+        let item = serde_json::from_str::<Item>(json).unwrap();
+        assert_eq!(item.value, 0);
+    }
+
+    #[test]
+    fn null_as_default_missing() {
+        let json = r#"
+        {
+            "no_value": 123
+        }
+        "#;
+        let item = serde_json::from_str::<Item>(json).unwrap();
+        assert_eq!(item.value, 0);
+    }
+}
+
 pub struct ToStr;
 
 impl<'de> Visitor<'de> for ToStr {
@@ -368,7 +453,8 @@ impl<'de> Visitor<'de> for ToStr {
     where
         E: SerdeError,
     {
-        Ok(s.to_owned())
+        // consistently with ToNum
+        Ok(s.trim().to_owned())
     }
 
     fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
@@ -454,6 +540,17 @@ mod test_str {
         assert_eq!(item.value, "123");
     }
 
+    #[test]
+    fn str_deserialize_string_with_whitespace() {
+        let json = r#"
+        {
+            "value": " 123 "
+        }
+        "#;
+        let item = serde_json::from_str::<Item>(json).unwrap();
+        assert_eq!(item.value, "123");
+    }
+
     #[test]
     fn str_deserialize_positive_f64() {
         let json = r#"
@@ -511,3 +608,118 @@ mod test_str_opt {
         assert_eq!(item.value, None);
     }
 }
+
+pub struct ToBool;
+
+impl<'de> Visitor<'de> for ToBool {
+    type Value = bool;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, r#"a boolean, 0 or 1, or a string of these"#)
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+    where
+        E: SerdeError,
+    {
+        Ok(v)
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: SerdeError,
+    {
+        match v {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(SerdeError::invalid_value(Unexpected::Unsigned(v), &self)),
+        }
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: SerdeError,
+    {
+        match v {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(SerdeError::invalid_value(Unexpected::Signed(v), &self)),
+        }
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: SerdeError,
+    {
+        match s.trim() {
+            "0" | "false" => Ok(false),
+            "1" | "true" => Ok(true),
+            _ => Err(SerdeError::invalid_value(Unexpected::Str(s), &self)),
+        }
+    }
+}
+
+impl ToBool {
+    pub fn deserialize<'de, D>(de: D) -> Result<bool, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        de.deserialize_any(ToBool {})
+    }
+
+    pub fn deserialize_opt<'de, D>(de: D) -> Result<Option<bool>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        de.deserialize_any(ToBool {})
+            .map_or(Ok(None), |b| Ok(Some(b)))
+    }
+}
+
+#[cfg(test)]
+mod test_bool {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug)]
+    struct Item {
+        #[serde(deserialize_with = "ToBool::deserialize")]
+        value: bool,
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct OptItem {
+        #[serde(default)]
+        #[serde(deserialize_with = "ToBool::deserialize_opt")]
+        value: Option<bool>,
+    }
+
+    #[test]
+    fn bool_deserialize_each_representation() {
+        for (json, expected) in &[
+            (r#"{"value": true}"#, true),
+            (r#"{"value": false}"#, false),
+            (r#"{"value": 1}"#, true),
+            (r#"{"value": 0}"#, false),
+            (r#"{"value": "1"}"#, true),
+            (r#"{"value": "false"}"#, false),
+        ] {
+            let item = serde_json::from_str::<Item>(json).unwrap();
+            assert_eq!(item.value, *expected, "{}", json);
+        }
+    }
+
+    #[test]
+    fn bool_dont_deserialize_other_numbers() {
+        assert!(serde_json::from_str::<Item>(r#"{"value": 2}"#).is_err());
+        assert!(serde_json::from_str::<Item>(r#"{"value": "yes"}"#).is_err());
+    }
+
+    #[test]
+    fn bool_deserialize_opt() {
+        let item = serde_json::from_str::<OptItem>(r#"{"value": 1}"#).unwrap();
+        assert_eq!(item.value, Some(true));
+        let item = serde_json::from_str::<OptItem>("{}").unwrap();
+        assert_eq!(item.value, None);
+    }
+}
@@ -2,7 +2,8 @@ use super::invariant_deserialize::ToNum;
 use super::*;
 
 /// <https://vk.com/dev/objects/link>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Link {
     pub url: String,
     pub title: String,
@@ -16,13 +17,15 @@ pub struct Link {
 }
 
 /// <https://vk.com/dev/link_product>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Product {
     pub price: Price,
 }
 
 /// <https://vk.com/dev/price>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Price {
     #[serde(deserialize_with = "ToNum::<Integer>::deserialize")]
     pub amount: Integer,
@@ -31,8 +34,80 @@ pub struct Price {
 }
 
 /// <https://vk.com/dev/price>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Currency {
     pub id: Integer,
     pub name: String,
 }
+
+/// <https://vk.com/dev/utils.getLinkStats>
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct LinkStats {
+    pub key: String,
+    pub stats: Vec<LinkStatsInterval>,
+}
+
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct LinkStatsInterval {
+    pub timestamp: Integer,
+    pub views: Integer,
+
+    // with `extended=1`
+    pub sex_age: Option<Vec<SexAge>>,
+    pub countries: Option<Vec<CountryViews>>,
+    pub cities: Option<Vec<CityViews>>,
+}
+
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct SexAge {
+    pub age_range: String,
+    pub female: Integer,
+    pub male: Integer,
+}
+
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct CountryViews {
+    pub country_id: Integer,
+    pub views: Integer,
+}
+
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct CityViews {
+    pub city_id: Integer,
+    pub views: Integer,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn link_stats() {
+        let json = r#"
+        {
+            "key": "8TDuIz",
+            "stats": [
+                {
+                    "timestamp": 1600000000,
+                    "views": 12,
+                    "sex_age": [{"age_range": "18-21", "female": 5, "male": 7}],
+                    "countries": [{"country_id": 1, "views": 12}],
+                    "cities": [{"city_id": 2, "views": 10}]
+                },
+                {"timestamp": 1600086400, "views": 0}
+            ]
+        }
+        "#;
+        let stats = serde_json::from_str::<LinkStats>(json).unwrap();
+        assert_eq!(stats.key, "8TDuIz");
+        assert_eq!(stats.stats[0].views, 12);
+        assert_eq!(stats.stats[0].sex_age.as_ref().unwrap()[0].male, 7);
+        assert!(stats.stats[1].cities.is_none());
+    }
+}
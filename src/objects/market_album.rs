@@ -1,7 +1,8 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/market_album>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct MarketAlbum {
     pub id: Integer,
     pub owner_id: Integer,
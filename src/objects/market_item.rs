@@ -1,7 +1,9 @@
+use super::invariant_deserialize::ToBool;
 use super::*;
 
 /// <https://vk.com/dev/objects/market_item>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct MarketItem {
     pub id: Integer,
     pub owner_id: Integer,
@@ -12,7 +14,8 @@ pub struct MarketItem {
     pub thumb_photo: String,
     pub date: Integer,
     pub availability: Integer,
-    pub is_favorite: Option<Boolean>,
+    #[serde(default, deserialize_with = "ToBool::deserialize_opt")]
+    pub is_favorite: Option<VkFlag>,
 
     // extended
     pub photos: Option<Vec<photo::Photo>>,
@@ -23,20 +26,23 @@ pub struct MarketItem {
     pub button_title: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Category {
     pub id: Integer,
     pub name: String,
     pub section: Section,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Section {
     pub id: Integer,
     pub name: String,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Likes {
     pub user_likes: Integer,
     pub count: Integer,
@@ -1,7 +1,8 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/message>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Message {
     pub id: Integer,
     pub date: Integer,
@@ -25,7 +26,8 @@ pub struct Message {
     pub is_cropped: Option<Boolean>, // might be Option<Integer>; from changelog v5.102 @ https://vk.com/dev/versions
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Action {
     #[serde(rename = "type")]
     pub type_: String,
@@ -36,7 +38,8 @@ pub struct Action {
     pub photo: Option<Photo>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Photo {
     pub photo_50: String,
     pub photo_100: String,
@@ -44,7 +47,8 @@ pub struct Photo {
 }
 
 /// <https://vk.com/dev/objects/pinned_message>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Pinned {
     pub id: Integer,
     pub date: Integer,
@@ -1,6 +1,10 @@
 use super::*;
+#[cfg(feature = "capture_unknown")]
+use serde_json::Value;
+use std::collections::HashMap;
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct NewsFeed {
     // массив новостей для текущего пользователя
     pub items: Option<Vec<Item>>,
@@ -15,7 +19,76 @@ pub struct NewsFeed {
     pub next_from: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+/// <https://vk.com/dev/newsfeed.search> with `extended=1`
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct SearchResult {
+    pub items: Vec<post::Post>,
+    #[serde(default)]
+    pub profiles: Vec<user::User>,
+    #[serde(default)]
+    pub groups: Vec<group::Group>,
+    // the number of the posts in this response
+    pub count: Option<Integer>,
+    // the total number of the posts found
+    pub total_count: Option<Integer>,
+    // start_from, который необходимо передать, для того, чтобы получить следующую часть результатов
+    pub next_from: Option<String>,
+}
+
+impl NewsFeed {
+    /// Indexes `profiles` by the user id and `groups` by the (positive) group id.
+    pub fn resolve(
+        &self,
+    ) -> (
+        HashMap<Integer, &user::User>,
+        HashMap<Integer, &group::Group>,
+    ) {
+        let users = self
+            .profiles
+            .iter()
+            .flatten()
+            .map(|user| (user.id, user))
+            .collect();
+        let groups = self
+            .groups
+            .iter()
+            .flatten()
+            .map(|group| (group.id, group))
+            .collect();
+        (users, groups)
+    }
+}
+
+/// The source of a news `Item`
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub enum Author<'a> {
+    User(&'a user::User),
+    Group(&'a group::Group),
+    /// Not in the `profiles` or `groups` of the feed; holds the `source_id`
+    Unknown(Integer),
+}
+
+impl<'a> Author<'a> {
+    /// Finds the user or the community of the `source_id` in the maps of [`NewsFeed::resolve`](struct.NewsFeed.html#method.resolve),
+    /// so that they are built once for all the items of a feed.
+    pub fn from_maps(
+        source_id: Integer,
+        users: &HashMap<Integer, &'a user::User>,
+        groups: &HashMap<Integer, &'a group::Group>,
+    ) -> Author<'a> {
+        let author = if source_id > 0 {
+            users.get(&source_id).copied().map(Author::User)
+        } else {
+            groups.get(&-source_id).copied().map(Author::Group)
+        };
+        author.unwrap_or(Author::Unknown(source_id))
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Item {
     // тип списка новости, соответствующий одному из значений параметра filters
     #[serde(rename = "type")]
@@ -55,6 +128,8 @@ pub struct Item {
     pub likes: Option<post::Likes>,
     // находится в записях со стен и содержит информацию о числе людей, которые скопировали данную запись на свою страницу
     pub reposts: Option<post::Reposts>,
+    // находится в записях со стен и содержит информацию о числе просмотров записи
+    pub views: Option<post::Views>,
     // находится в записях со стен и содержит массив объектов, которые прикреплены к текущей новости (фотография, ссылка и т.п.).
     // Более подробная информация представлена на странице <https://vk.com/dev/objects/attachments_w>
     pub attachments: Option<Vec<NewsAttachment>>,
@@ -70,9 +145,45 @@ pub struct Item {
     pub friends: Option<FriendSet>,
 }
 
+impl Item {
+    /// The contents of the `attachments`, if any.
+    pub fn attachment_contents(&self) -> impl Iterator<Item = AttachmentContent<'_>> {
+        self.attachments
+            .iter()
+            .flatten()
+            .map(NewsAttachment::content)
+    }
+
+    /// The number of views of the post, if known.
+    pub fn view_count(&self) -> Option<Integer> {
+        self.views.as_ref().map(|views| views.count)
+    }
+
+    /// Finds the user or the community of the `source_id` in the `profiles` or `groups` of the `feed`.
+    ///
+    /// It scans the `feed`; for the authors of many items use [`Author::from_maps`](enum.Author.html#method.from_maps).
+    pub fn author<'a>(&self, feed: &'a NewsFeed) -> Author<'a> {
+        let author = if self.source_id > 0 {
+            feed.profiles
+                .iter()
+                .flatten()
+                .find(|user| user.id == self.source_id)
+                .map(Author::User)
+        } else {
+            feed.groups
+                .iter()
+                .flatten()
+                .find(|group| group.id == -self.source_id)
+                .map(Author::Group)
+        };
+        author.unwrap_or(Author::Unknown(self.source_id))
+    }
+}
+
 /// undocumented, differs from WallAttachment <https://vk.com/dev/objects/attachments_w> by album
 /// which does not equal to album::Album (id: String)
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct NewsAttachment {
     #[serde(rename = "type")]
     pub type_: String,
@@ -133,11 +244,82 @@ pub struct NewsAttachment {
 
     // type = podcast
     pub podcast: Option<podcast::Podcast>,
+
+    // the fields of the types unknown to rvk, keyed by the type
+    #[cfg(feature = "capture_unknown")]
+    #[serde(flatten)]
+    pub unknown: HashMap<String, Value>,
+}
+
+/// The content of a `NewsAttachment` selected by its `type_`.
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub enum AttachmentContent<'a> {
+    Photo(&'a photo::Photo),
+    PostedPhoto(&'a attachment::PostedPhoto),
+    Video(&'a video::Video),
+    Audio(&'a audio::Audio),
+    Doc(&'a document::Document),
+    Graffiti(&'a attachment::Graffiti),
+    Link(&'a link::Link),
+    Note(&'a note::Note),
+    App(&'a attachment::App),
+    Poll(&'a poll::Poll),
+    Page(&'a page::Page),
+    Album(&'a photo::Album),
+    PhotosList(&'a [String]),
+    Market(&'a market_item::MarketItem),
+    MarketAlbum(&'a market_album::MarketAlbum),
+    Sticker(&'a sticker::Sticker),
+    PrettyCards(&'a [attachment::Card]),
+    Event(&'a attachment::Event),
+    Podcast(&'a podcast::Podcast),
+    /// A type unknown to rvk (or a known one missing its field),
+    /// with the JSON of its field under the `capture_unknown` feature
+    Unknown {
+        type_: &'a str,
+        #[cfg(feature = "capture_unknown")]
+        raw: &'a Value,
+    },
+}
+
+impl NewsAttachment {
+    /// The field matching the `type_`.
+    pub fn content(&self) -> AttachmentContent<'_> {
+        use AttachmentContent as C;
+        let known = match self.type_.as_str() {
+            "photo" => self.photo.as_ref().map(C::Photo),
+            "posted_photo" => self.posted_photo.as_ref().map(C::PostedPhoto),
+            "video" => self.video.as_ref().map(C::Video),
+            "audio" => self.audio.as_ref().map(C::Audio),
+            "doc" => self.doc.as_ref().map(C::Doc),
+            "graffiti" => self.graffiti.as_ref().map(C::Graffiti),
+            "link" => self.link.as_ref().map(C::Link),
+            "note" => self.note.as_ref().map(C::Note),
+            "app" => self.app.as_ref().map(C::App),
+            "poll" => self.poll.as_ref().map(C::Poll),
+            "page" => self.page.as_ref().map(C::Page),
+            "album" => self.album.as_ref().map(C::Album),
+            "photos_list" => self.photos_list.as_deref().map(C::PhotosList),
+            "market" => self.market.as_ref().map(C::Market),
+            "market_album" => self.market_album.as_ref().map(C::MarketAlbum),
+            "sticker" => self.sticker.as_ref().map(C::Sticker),
+            "pretty_cards" => self.cards.as_deref().map(C::PrettyCards),
+            "event" => self.event.as_ref().map(C::Event),
+            "podcast" => self.podcast.as_ref().map(C::Podcast),
+            _ => None,
+        };
+        known.unwrap_or_else(|| C::Unknown {
+            type_: &self.type_,
+            #[cfg(feature = "capture_unknown")]
+            raw: self.unknown.get(&self.type_).unwrap_or(&Value::Null),
+        })
+    }
 }
 
 // specific for newsfeed types
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct PhotoSet {
     // информация о количестве объектов
     pub count: i64,
@@ -145,7 +327,8 @@ pub struct PhotoSet {
     pub items: Option<Vec<photo::Photo>>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct PhotoTags {
     // информация о количестве объектов
     pub count: i64,
@@ -153,7 +336,8 @@ pub struct PhotoTags {
     //pub items: Option<Vec<?>>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct NoteSet {
     // информация о количестве объектов
     pub count: i64,
@@ -161,12 +345,14 @@ pub struct NoteSet {
     pub items: Option<Vec<note::Note>>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct FriendItem {
     pub user_id: i64,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct FriendSet {
     // информация о количестве объектов
     pub count: i64,
@@ -174,7 +360,8 @@ pub struct FriendSet {
     pub items: Option<Vec<FriendItem>>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct HistoryItem {
     pub date: u64,
     pub from_id: i64,
@@ -190,3 +377,173 @@ pub struct HistoryItem {
     // source
     pub post_source: Option<post_source::PostSource>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed() -> NewsFeed {
+        serde_json::from_str(
+            r#"
+            {
+                "items": [
+                    {
+                        "type": "post", "source_id": 1, "date": 1600000000, "post_id": 10,
+                        "likes": {"count": 2, "user_likes": 0, "can_like": 1},
+                        "reposts": {"count": 1, "user_reposted": 0},
+                        "views": {"count": 150}
+                    },
+                    {"type": "post", "source_id": -2, "date": 1600000001, "post_id": 20},
+                    {"type": "post", "source_id": 3, "date": 1600000002, "post_id": 30}
+                ],
+                "profiles": [{"id": 1, "first_name": "Pavel", "last_name": "Durov"}],
+                "groups": [
+                    {
+                        "id": 2, "name": "VK API", "screen_name": "apiclub", "is_closed": 0,
+                        "type": "group", "photo_50": "", "photo_100": "", "photo_200": ""
+                    }
+                ]
+            }
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn search_result() {
+        let json = r#"
+        {
+            "items": [
+                {
+                    "id": 5, "owner_id": -2, "from_id": -2, "date": 1600000000,
+                    "text": "rust news", "post_type": "post", "marked_as_ads": 0,
+                    "comments": {"count": 0, "can_post": 1},
+                    "likes": {"count": 1, "user_likes": 0, "can_like": 1, "can_publish": 1},
+                    "reposts": {"count": 0, "user_reposted": 0},
+                    "views": {"count": 10}
+                }
+            ],
+            "profiles": [],
+            "groups": [
+                {
+                    "id": 2, "name": "VK API", "screen_name": "apiclub", "is_closed": 0,
+                    "type": "group", "photo_50": "", "photo_100": "", "photo_200": ""
+                }
+            ],
+            "count": 1,
+            "total_count": 1000,
+            "next_from": "5/-2_5"
+        }
+        "#;
+        let result = serde_json::from_str::<SearchResult>(json).unwrap();
+        assert_eq!(result.items[0].text, "rust news");
+        assert_eq!(result.total_count, Some(1000));
+        assert_eq!(result.next_from.as_deref(), Some("5/-2_5"));
+        assert_eq!(result.groups[0].id, 2);
+    }
+
+    #[test]
+    fn resolve_profiles_and_groups() {
+        let feed = feed();
+        let (users, groups) = feed.resolve();
+        assert_eq!(users[&1].first_name.as_deref(), Some("Pavel"));
+        assert_eq!(groups[&2].screen_name, "apiclub");
+    }
+
+    #[test]
+    fn view_count_of_items() {
+        let feed = feed();
+        let items = feed.items.as_ref().unwrap();
+        assert_eq!(items[0].view_count(), Some(150));
+        assert_eq!(items[0].reposts.as_ref().unwrap().count, 1);
+        assert_eq!(items[1].view_count(), None);
+    }
+
+    #[test]
+    fn author_of_items() {
+        let feed = feed();
+        let items = feed.items.as_ref().unwrap();
+        match items[0].author(&feed) {
+            Author::User(user) => assert_eq!(user.id, 1),
+            _ => unreachable!(),
+        }
+        match items[1].author(&feed) {
+            Author::Group(group) => assert_eq!(group.id, 2),
+            _ => unreachable!(),
+        }
+        match items[2].author(&feed) {
+            Author::Unknown(source_id) => assert_eq!(source_id, items[2].source_id),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn authors_from_maps() {
+        let feed = feed();
+        let (users, groups) = feed.resolve();
+        let authors: Vec<_> = feed
+            .items
+            .iter()
+            .flatten()
+            .map(|item| Author::from_maps(item.source_id, &users, &groups))
+            .collect();
+        assert!(matches!(authors[0], Author::User(user) if user.id == 1));
+        assert!(matches!(authors[1], Author::Group(group) if group.id == 2));
+        assert!(matches!(authors[2], Author::Unknown(_)));
+    }
+
+    #[test]
+    fn attachment_content() {
+        let attachment: NewsAttachment = serde_json::from_str(
+            r#"{"type": "link", "link": {"url": "https://vk.com", "title": "VK"}}"#,
+        )
+        .unwrap();
+        match attachment.content() {
+            AttachmentContent::Link(link) => assert_eq!(link.url, "https://vk.com"),
+            _ => unreachable!(),
+        }
+        #[cfg(feature = "capture_unknown")]
+        assert!(attachment.unknown.is_empty());
+    }
+
+    #[test]
+    fn attachment_contents_of_item() {
+        let item: Item = serde_json::from_str(
+            r#"
+            {
+                "type": "post", "source_id": 1, "date": 1600000000, "post_id": 10,
+                "attachments": [
+                    {"type": "photo", "photo": {"id": 1, "sizes": []}},
+                    {"type": "link", "link": {"url": "https://vk.com", "title": "VK"}}
+                ]
+            }
+            "#,
+        )
+        .unwrap();
+        let contents: Vec<_> = item.attachment_contents().collect();
+        assert_eq!(contents.len(), 2);
+        assert!(matches!(contents[0], AttachmentContent::Photo(photo) if photo.id == 1));
+        assert!(matches!(contents[1], AttachmentContent::Link(link) if link.title == "VK"));
+
+        let feed = feed();
+        let items = feed.items.as_ref().unwrap();
+        assert_eq!(items[0].attachment_contents().count(), 0);
+    }
+
+    #[test]
+    fn attachment_content_unknown() {
+        let attachment: NewsAttachment =
+            serde_json::from_str(r#"{"type": "new_thing", "new_thing": {"id": 1, "title": "?"}}"#)
+                .unwrap();
+        match attachment.content() {
+            #[cfg(feature = "capture_unknown")]
+            AttachmentContent::Unknown { type_, raw } => {
+                assert_eq!(type_, "new_thing");
+                assert_eq!(raw["title"], "?");
+            }
+            #[cfg(not(feature = "capture_unknown"))]
+            AttachmentContent::Unknown { type_ } => assert_eq!(type_, "new_thing"),
+            _ => unreachable!(),
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/note>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Note {
     pub id: Integer,
     pub owner_id: Integer,
@@ -1,7 +1,8 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/page>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Page {
     pub id: Integer,
     pub group_id: Integer,
@@ -2,7 +2,8 @@ use super::invariant_deserialize::ToNum;
 use super::*;
 
 /// <https://vk.com/dev/objects/photo>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Photo {
     pub id: Integer,
     pub album_id: Option<Integer>,
@@ -26,8 +27,19 @@ pub struct Photo {
     pub access_key: Option<String>,
 }
 
+impl Photo {
+    /// Returns the largest of the `sizes` (by area).
+    pub fn largest(&self) -> Option<&Size> {
+        self.sizes
+            .iter()
+            .flatten()
+            .max_by_key(|size| size.width.unwrap_or(0) * size.height.unwrap_or(0))
+    }
+}
+
 /// <https://vk.com/dev/photo_sizes>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Size {
     // normally used
     pub url: Option<String>,
@@ -40,14 +52,16 @@ pub struct Size {
     pub type_: String,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Cropped {
     pub photo: Photo,
     pub crop: Rect,
     pub rect: Rect,
 }
 
-#[derive(Deserialize, Copy, Clone, Debug)]
+#[derive(Deserialize, Copy, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Rect {
     pub x: Number,
     pub y: Number,
@@ -56,15 +70,25 @@ pub struct Rect {
 }
 
 /// <https://vk.com/dev/objects/attachments_w>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Album {
     #[serde(deserialize_with = "ToNum::<Integer>::deserialize")]
     pub id: Integer,
-    pub thumb: photo::Photo,
+    // in the attachments
+    pub thumb: Option<photo::Photo>,
+    // in `photos.getAlbums`
+    pub thumb_id: Option<Integer>,
+    // in `photos.getAlbums` with `need_covers=1`
+    pub thumb_src: Option<String>,
     pub owner_id: Integer,
     pub title: String,
+    // not present in the system albums
+    #[serde(default)]
     pub description: String,
+    #[serde(default)]
     pub created: Integer,
+    #[serde(default)]
     pub updated: Integer,
     pub size: Integer,
 }
@@ -72,9 +96,57 @@ pub struct Album {
 /// <https://vk.com/dev/objects/attachments_w>
 /// or
 /// <https://vk.com/dev/objects/sticker>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Image {
     pub url: String,
     pub width: Integer,
     pub height: Integer,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::response::ListResponse;
+
+    #[test]
+    fn largest_size() {
+        let json = r#"
+        {
+            "id": 1,
+            "sizes": [
+                {"url": "https://vk.com/m.jpg", "width": 130, "height": 98, "type": "m"},
+                {"url": "https://vk.com/y.jpg", "width": 807, "height": 605, "type": "y"},
+                {"url": "https://vk.com/x.jpg", "width": 604, "height": 453, "type": "x"}
+            ]
+        }
+        "#;
+        let photo = serde_json::from_str::<Photo>(json).unwrap();
+        assert_eq!(photo.largest().unwrap().type_, "y");
+    }
+
+    #[test]
+    fn albums_with_numeric_and_string_ids() {
+        let json = r#"
+        {
+            "count": 2,
+            "items": [
+                {
+                    "id": 123, "thumb_id": 456, "owner_id": 1, "title": "Summer",
+                    "description": "", "created": 1600000000, "updated": 1600000001,
+                    "size": 10, "thumb_src": "https://vk.com/t.jpg"
+                },
+                {"id": "-6", "owner_id": 1, "title": "Profile photos", "size": 3}
+            ]
+        }
+        "#;
+        let albums = serde_json::from_str::<ListResponse<Album>>(json).unwrap();
+        assert_eq!(albums.items[0].id, 123);
+        assert_eq!(
+            albums.items[0].thumb_src.as_deref(),
+            Some("https://vk.com/t.jpg")
+        );
+        assert_eq!(albums.items[1].id, -6);
+        assert!(albums.items[1].thumb.is_none());
+    }
+}
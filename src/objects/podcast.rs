@@ -1,9 +1,83 @@
 use super::*;
 
 /// Undocumented
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Podcast {
-    pub url: String,
+    pub id: Integer,
+    pub owner_id: Integer,
+    pub episode_id: Option<Integer>,
     pub title: String,
+    #[serde(default)]
+    pub url: String,
+    pub cover: Option<Cover>,
+    #[serde(default)]
+    pub duration: Integer,
+    /// The episode audio, if attached
+    pub audio: Option<audio::Audio>,
     pub access_key: Option<String>,
 }
+
+impl Podcast {
+    /// Returns the URL of the narrowest cover image at least `width` pixels wide,
+    /// or of the widest one if none is wide enough.
+    pub fn cover_url(&self, width: Integer) -> Option<&str> {
+        let sizes = &self.cover.as_ref()?.sizes;
+        sizes
+            .iter()
+            .filter(|image| image.width >= width)
+            .min_by_key(|image| image.width)
+            .or_else(|| sizes.iter().max_by_key(|image| image.width))
+            .map(|image| image.url.as_str())
+    }
+}
+
+/// Undocumented
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct Cover {
+    #[serde(default)]
+    pub sizes: Vec<photo::Image>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::objects::newsfeed::{AttachmentContent, NewsAttachment};
+
+    #[test]
+    fn podcast_attachment() {
+        let attachment: NewsAttachment = serde_json::from_str(
+            r#"{
+                "type": "podcast",
+                "podcast": {
+                    "id": 456239017,
+                    "owner_id": -1,
+                    "episode_id": 17,
+                    "title": "Episode 17",
+                    "duration": 1800,
+                    "cover": {"sizes": [
+                        {"url": "https://vk.com/80.jpg", "width": 80, "height": 80, "type": "a"},
+                        {"url": "https://vk.com/600.jpg", "width": 600, "height": 600, "type": "c"},
+                        {"url": "https://vk.com/200.jpg", "width": 200, "height": 200, "type": "b"}
+                    ]},
+                    "audio": {
+                        "id": 456239017, "owner_id": -1, "artist": "VK", "title": "Episode 17",
+                        "duration": 1800, "url": "https://vk.com/17.mp3", "date": 1600000000
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let podcast = match attachment.content() {
+            AttachmentContent::Podcast(podcast) => podcast,
+            _ => unreachable!(),
+        };
+        assert_eq!(podcast.episode_id, Some(17));
+        assert_eq!(podcast.duration, 1800);
+        assert_eq!(podcast.audio.as_ref().unwrap().url, "https://vk.com/17.mp3");
+        assert_eq!(podcast.cover_url(100), Some("https://vk.com/200.jpg"));
+        assert_eq!(podcast.cover_url(80), Some("https://vk.com/80.jpg"));
+        assert_eq!(podcast.cover_url(1000), Some("https://vk.com/600.jpg"));
+    }
+}
@@ -1,32 +1,36 @@
+use super::invariant_deserialize::null_as_default;
 use super::*;
 
 /// <https://vk.com/dev/objects/poll>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Poll {
     pub id: Integer,
     pub owner_id: Integer,
     pub created: Integer,
     pub question: String,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "null_as_default")]
     pub votes: Integer,
     pub answers: Vec<Answer>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "null_as_default")]
     pub anonymous: Boolean,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "null_as_default")]
     pub multiple: Boolean,
     pub answer_ids: Option<Vec<Integer>>,
+    // 0 or missing for polls without an end date
+    #[serde(default, deserialize_with = "null_as_default")]
     pub end_date: Integer,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "null_as_default")]
     pub closed: Boolean,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "null_as_default")]
     pub is_board: Boolean,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "null_as_default")]
     pub can_edit: Boolean,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "null_as_default")]
     pub can_vote: Boolean,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "null_as_default")]
     pub can_report: Boolean,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "null_as_default")]
     pub can_share: Boolean,
     pub author_id: Option<Integer>, // optional at least in newsfeed
     pub photo: Option<photo::Photo>,
@@ -34,7 +38,8 @@ pub struct Poll {
     pub friends: Option<Vec<PollFriend>>, // optional at least in newsfeed
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Answer {
     pub id: Integer,
     pub text: String,
@@ -42,7 +47,8 @@ pub struct Answer {
     pub rate: Number,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Background {
     pub id: Integer,
 
@@ -57,13 +63,58 @@ pub struct Background {
     pub points: Vec<Option<GradientPoint>>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct GradientPoint {
     pub position: Number,
     pub color: String,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct PollFriend {
     pub id: Integer,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poll_with_nulls() {
+        let json = r#"
+        {
+            "id": 1,
+            "owner_id": -1,
+            "created": 1600000000,
+            "question": "?",
+            "votes": null,
+            "answers": [{"id": 1, "text": "yes", "votes": 0, "rate": 0.0}],
+            "anonymous": null,
+            "multiple": false,
+            "end_date": null,
+            "closed": null
+        }
+        "#;
+        let poll = serde_json::from_str::<Poll>(json).unwrap();
+        assert_eq!(poll.votes, 0);
+        assert_eq!(poll.end_date, 0);
+        assert!(!poll.anonymous);
+        assert!(!poll.closed);
+    }
+
+    #[test]
+    fn poll_without_end_date() {
+        let json = r#"
+        {
+            "id": 1,
+            "owner_id": -1,
+            "created": 1600000000,
+            "question": "?",
+            "answers": []
+        }
+        "#;
+        let poll = serde_json::from_str::<Poll>(json).unwrap();
+        assert_eq!(poll.end_date, 0);
+    }
+}
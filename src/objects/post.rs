@@ -1,7 +1,8 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/post>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Post {
     pub id: Integer,
 
@@ -35,28 +36,34 @@ pub struct Post {
     pub is_favorite: Option<Boolean>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Comments {
     pub count: Integer,
     pub can_post: Integer,
     pub groups_can_post: Option<Boolean>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Likes {
     pub count: Integer,
     pub user_likes: Integer,
     pub can_like: Integer,
+    // missing in the likes of comments
+    #[serde(default)]
     pub can_publish: Integer,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Reposts {
     pub count: Integer,
     pub user_reposted: Integer,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Views {
     pub count: Integer,
 }
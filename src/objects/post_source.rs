@@ -1,7 +1,8 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/post_source>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct PostSource {
     #[serde(rename = "type")]
     pub type_: String,
@@ -1,7 +1,8 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/push_settings>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct PushSettings {
     pub msg: Option<String>,
     pub chat: Option<String>,
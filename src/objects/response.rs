@@ -0,0 +1,22 @@
+use super::*;
+
+/// The `{count, items}` envelope of the list-returning methods.
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct ListResponse<T> {
+    pub count: Integer,
+    pub items: Vec<T>,
+}
+
+/// The `{count, items, profiles, groups}` envelope of the methods called with `extended=1`:
+/// `profiles` and `groups` hold the users and communities referenced by the `items`.
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+pub struct ExtendedResponse<T> {
+    pub count: Option<Integer>,
+    pub items: Vec<T>,
+    #[serde(default)]
+    pub profiles: Vec<user::User>,
+    #[serde(default)]
+    pub groups: Vec<group::Group>,
+}
@@ -0,0 +1,79 @@
+use super::*;
+
+/// The object a screen name resolves to (<https://vk.com/dev/utils.resolveScreenName>)
+#[derive(Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+#[serde(from = "RawResolvedObject")]
+pub enum ResolvedObject {
+    User(Integer),
+    Group(Integer),
+    Application(Integer),
+    Page(Integer),
+    /// A type unknown to rvk, e.g. `vk_app` or `internal_vkui`
+    Other {
+        type_: String,
+        object_id: Integer,
+    },
+}
+
+impl ResolvedObject {
+    /// The id of the object (positive for the communities too).
+    pub fn id(&self) -> Integer {
+        match *self {
+            ResolvedObject::User(id)
+            | ResolvedObject::Group(id)
+            | ResolvedObject::Application(id)
+            | ResolvedObject::Page(id)
+            | ResolvedObject::Other { object_id: id, .. } => id,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawResolvedObject {
+    #[serde(rename = "type")]
+    type_: String,
+    object_id: Integer,
+}
+
+impl From<RawResolvedObject> for ResolvedObject {
+    fn from(raw: RawResolvedObject) -> Self {
+        match raw.type_.as_str() {
+            "user" => ResolvedObject::User(raw.object_id),
+            "group" => ResolvedObject::Group(raw.object_id),
+            "application" => ResolvedObject::Application(raw.object_id),
+            "page" => ResolvedObject::Page(raw.object_id),
+            _ => ResolvedObject::Other {
+                type_: raw.type_,
+                object_id: raw.object_id,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolved_user() {
+        let resolved: ResolvedObject =
+            serde_json::from_str(r#"{"type": "user", "object_id": 1}"#).unwrap();
+        assert!(resolved == ResolvedObject::User(1));
+        assert_eq!(resolved.id(), 1);
+    }
+
+    #[test]
+    fn resolved_unknown_type() {
+        let resolved: ResolvedObject =
+            serde_json::from_str(r#"{"type": "vk_app", "object_id": 7}"#).unwrap();
+        assert!(
+            resolved
+                == ResolvedObject::Other {
+                    type_: "vk_app".into(),
+                    object_id: 7
+                }
+        );
+        assert_eq!(resolved.id(), 7);
+    }
+}
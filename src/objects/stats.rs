@@ -1,7 +1,8 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/stats_format>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Stats {
     pub period_from: String,
     pub period_to: String,
@@ -9,13 +10,15 @@ pub struct Stats {
     pub reach: Reach,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Visitors {
     pub views: Integer,
     pub visitors: Integer,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Reach {
     pub reach: Integer,
     pub reach_subscribers: Integer,
@@ -27,20 +30,23 @@ pub struct Reach {
     pub countries: Vec<CountriesStats>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct SpecificStats {
     pub value: String,
     pub count: Integer,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct CitiesStats {
     pub name: String,
     pub city_id: String,
     pub count: Integer,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct CountriesStats {
     pub name: String,
     pub code: String,
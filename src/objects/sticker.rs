@@ -1,7 +1,8 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/sticker>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Sticker {
     pub product_id: Option<Integer>,
     pub sticker_id: Integer,
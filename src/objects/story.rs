@@ -1,7 +1,8 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/story>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Story {
     pub id: Integer,
     pub owner_id: Integer,
@@ -28,13 +29,15 @@ pub struct Story {
     pub access_key: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Link {
     pub text: String,
     pub url: String,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Replies {
     pub count: Integer,
     pub new: Option<Integer>,
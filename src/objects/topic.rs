@@ -1,7 +1,8 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/topic>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Topic {
     pub id: Integer,
     pub title: String,
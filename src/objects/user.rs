@@ -1,15 +1,19 @@
-use super::invariant_deserialize::ToStr;
+use super::invariant_deserialize::{ToBool, ToStr};
 use super::*;
 
 /// <https://vk.com/dev/objects/user>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct User {
     // Main fields
+    // only `id` is guaranteed: the rest depends on the `fields` requested and
+    // may be missing altogether for deactivated users
     pub id: Integer,
-    pub first_name: String,
-    pub last_name: String,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
     pub deactivated: Option<String>,
-    pub is_closed: Option<Boolean>,
+    #[serde(default, deserialize_with = "ToBool::deserialize_opt")]
+    pub is_closed: Option<VkFlag>,
     pub can_access_closed: Option<Boolean>,
 
     // Optional fields A-L
@@ -66,9 +70,11 @@ pub struct User {
     pub has_photo: Option<Integer>,
     pub home_town: Option<String>,
     pub interests: Option<String>,
-    pub is_favorite: Option<Integer>,
+    #[serde(default, deserialize_with = "ToBool::deserialize_opt")]
+    pub is_favorite: Option<VkFlag>,
     pub is_friend: Option<Integer>,
-    pub is_hidden_from_feed: Option<Integer>,
+    #[serde(default, deserialize_with = "ToBool::deserialize_opt")]
+    pub is_hidden_from_feed: Option<VkFlag>,
 
     // last_name_{case}
     pub last_name_nom: Option<String>,
@@ -123,14 +129,29 @@ pub struct User {
     pub status_audio: Option<audio::Audio>,
 
     pub timezone: Option<Integer>,
-    pub trending: Option<Integer>,
+    #[serde(default, deserialize_with = "ToBool::deserialize_opt")]
+    pub trending: Option<VkFlag>,
     pub tv: Option<String>,
     pub universities: Option<Vec<University>>,
-    pub verified: Option<Integer>,
+    #[serde(default, deserialize_with = "ToBool::deserialize_opt")]
+    pub verified: Option<VkFlag>,
     pub wall_default: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+impl User {
+    /// Whether the user is online, as per the `online` field.
+    pub fn is_online(&self) -> bool {
+        self.online == Some(1)
+    }
+
+    /// Whether the user is online from a mobile device, as per the `online_mobile` field.
+    pub fn is_online_mobile(&self) -> bool {
+        self.online_mobile == Some(1)
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Career {
     pub group_id: Option<Integer>,
     pub company: Option<String>,
@@ -142,13 +163,15 @@ pub struct Career {
     pub position: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Contacts {
     pub mobile_phone: Option<String>,
     pub home_phone: Option<String>,
 }
 
-#[derive(Deserialize, Copy, Clone, Debug)]
+#[derive(Deserialize, Copy, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Counters {
     pub albums: Option<Integer>,
     pub videos: Option<Integer>,
@@ -164,7 +187,8 @@ pub struct Counters {
     pub pages: Option<Integer>,
 }
 
-#[derive(Deserialize, Copy, Clone, Debug)]
+#[derive(Deserialize, Copy, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Exports {
     pub twitter: Option<Integer>,
     pub facebook: Option<Integer>,
@@ -172,13 +196,45 @@ pub struct Exports {
     pub instagram: Option<Integer>,
 }
 
-#[derive(Deserialize, Copy, Clone, Debug)]
+#[derive(Deserialize, Copy, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct LastSeen {
     pub time: Option<Integer>,
-    pub platform: Option<Integer>,
+    pub platform: Option<Platform>,
+}
+
+/// The platform the user was last seen on
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "debug", derive(Debug))]
+#[serde(from = "Integer")]
+pub enum Platform {
+    Mobile,
+    IPhone,
+    IPad,
+    Android,
+    WindowsPhone,
+    Windows,
+    Web,
+    Unknown(Integer),
 }
 
-#[derive(Deserialize, Clone, Debug)]
+impl From<Integer> for Platform {
+    fn from(id: Integer) -> Self {
+        match id {
+            1 => Self::Mobile,
+            2 => Self::IPhone,
+            3 => Self::IPad,
+            4 => Self::Android,
+            5 => Self::WindowsPhone,
+            6 => Self::Windows,
+            7 => Self::Web,
+            _ => Self::Unknown(id),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Military {
     pub unit: Option<String>,
     pub unit_id: Option<Integer>,
@@ -187,7 +243,8 @@ pub struct Military {
     pub until: Option<Integer>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Occupation {
     #[serde(rename = "type")]
     pub type_: Option<String>,
@@ -196,7 +253,8 @@ pub struct Occupation {
     pub name: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Personal {
     pub political: Option<Integer>,
     pub langs: Option<Vec<String>>,
@@ -208,7 +266,8 @@ pub struct Personal {
     pub alcohol: Option<Integer>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Relative {
     pub id: Option<Integer>,
     pub name: Option<String>,
@@ -217,7 +276,8 @@ pub struct Relative {
     pub type_: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct School {
     pub id: Option<String>,
     pub country: Option<Integer>,
@@ -235,7 +295,8 @@ pub struct School {
     pub type_str: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct University {
     pub id: Option<Integer>,
     pub country: Option<Integer>,
@@ -249,3 +310,77 @@ pub struct University {
     pub education_form: Option<String>,
     pub education_status: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimal_user() {
+        let json = r#"
+        {
+            "id": 1
+        }
+        "#;
+        let user = serde_json::from_str::<User>(json).unwrap();
+        assert_eq!(user.id, 1);
+        assert_eq!(user.first_name, None);
+        assert_eq!(user.last_name, None);
+    }
+
+    #[test]
+    fn deactivated_user() {
+        let json = r#"
+        {
+            "id": 2,
+            "first_name": "DELETED",
+            "last_name": "",
+            "deactivated": "deleted"
+        }
+        "#;
+        let user = serde_json::from_str::<User>(json).unwrap();
+        assert_eq!(user.id, 2);
+        assert_eq!(user.first_name.as_deref(), Some("DELETED"));
+        assert_eq!(user.deactivated.as_deref(), Some("deleted"));
+        assert_eq!(user.is_closed, None);
+    }
+
+    #[test]
+    fn user_flags_as_numbers_and_booleans() {
+        let numbers =
+            serde_json::from_str::<User>(r#"{"id": 1, "is_closed": 0, "verified": 1}"#).unwrap();
+        assert_eq!(numbers.is_closed, Some(false));
+        assert_eq!(numbers.verified, Some(true));
+        let booleans =
+            serde_json::from_str::<User>(r#"{"id": 1, "is_closed": true, "verified": false}"#)
+                .unwrap();
+        assert_eq!(booleans.is_closed, Some(true));
+        assert_eq!(booleans.verified, Some(false));
+    }
+
+    #[test]
+    fn user_with_last_seen() {
+        let json = r#"
+        {
+            "id": 1,
+            "online": 1,
+            "online_mobile": 1,
+            "online_app": 2274003,
+            "last_seen": {"time": 1600000000, "platform": 4}
+        }
+        "#;
+        let user = serde_json::from_str::<User>(json).unwrap();
+        assert!(user.is_online());
+        assert!(user.is_online_mobile());
+        assert_eq!(user.online_app.as_deref(), Some("2274003"));
+        let last_seen = user.last_seen.unwrap();
+        assert_eq!(last_seen.time, Some(1600000000));
+        assert!(last_seen.platform == Some(Platform::Android));
+    }
+
+    #[test]
+    fn unknown_platform() {
+        let last_seen = serde_json::from_str::<LastSeen>(r#"{"platform": 42}"#).unwrap();
+        assert!(last_seen.platform == Some(Platform::Unknown(42)));
+    }
+}
@@ -1,7 +1,8 @@
 use super::*;
 
 /// <https://vk.com/dev/objects/video>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct Video {
     pub id: Integer,
     pub owner_id: Integer,
@@ -44,7 +45,8 @@ pub struct Video {
 }
 
 /// <https://vk.com/dev/objects/video_image>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct VideoImage {
     pub url: String,
     pub width: Integer,
@@ -53,7 +55,8 @@ pub struct VideoImage {
 }
 
 /// <https://vk.com/dev/objects/video_album_full>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct VideoPlaylist {
     pub id: Integer,
     pub owner_id: Integer,
@@ -68,7 +71,8 @@ pub struct VideoPlaylist {
 }
 
 /// <https://vk.com/dev/objects/video_cat_element>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct CatalogElement {
     pub id: Integer,
     pub owner_id: Integer,
@@ -96,7 +100,8 @@ pub struct CatalogElement {
 }
 
 /// <https://vk.com/dev/objects/video_cat_block>
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone)]
+#[cfg_attr(feature = "debug", derive(Debug))]
 pub struct CatalogBlock {
     pub items: Vec<CatalogElement>,
     pub id: Integer,
@@ -0,0 +1,166 @@
+//! Spreads the calls over several clients, e.g. set up with different proxies
+
+use crate::{api::APIClient, error::Result, Params};
+use serde::de::DeserializeOwned;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// How a [`PooledClient`](struct.PooledClient.html) picks the client for a call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dispatch {
+    /// The clients in turn.
+    RoundRobin,
+    /// The client with the fewest calls in progress (the first one of those on a tie).
+    LeastLoaded,
+}
+
+/// Several [`APIClient`](../api/struct.APIClient.html)s dispatching the calls between them.
+///
+/// ```no_run
+/// # use rvk::{pool::{Dispatch, PooledClient}, APIClient};
+/// # fn main() -> Result<(), reqwest::Error> {
+/// let clients = vec![
+///     APIClient::builder("token").proxy(reqwest::Proxy::all("http://10.0.0.1:3128")?).build(),
+///     APIClient::builder("token").proxy(reqwest::Proxy::all("http://10.0.0.2:3128")?).build(),
+/// ];
+/// let pool = PooledClient::new(clients).dispatch(Dispatch::LeastLoaded);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PooledClient {
+    clients: Vec<APIClient>,
+    in_progress: Vec<AtomicUsize>,
+    next: AtomicUsize,
+    dispatch: Dispatch,
+}
+
+impl PooledClient {
+    /// Creates a pool of the `clients` dispatching round-robin.
+    ///
+    /// # Panics
+    /// This method panics if `clients` is empty.
+    pub fn new(clients: Vec<APIClient>) -> Self {
+        assert!(!clients.is_empty(), "a pool needs at least one client");
+        Self {
+            in_progress: clients.iter().map(|_| AtomicUsize::new(0)).collect(),
+            clients,
+            next: AtomicUsize::new(0),
+            dispatch: Dispatch::RoundRobin,
+        }
+    }
+
+    /// Sets how the client for a call is picked.
+    pub fn dispatch(mut self, dispatch: Dispatch) -> Self {
+        self.dispatch = dispatch;
+        self
+    }
+
+    /// The clients of the pool.
+    pub fn clients(&self) -> &[APIClient] {
+        &self.clients
+    }
+
+    /// Picks the next client, e.g. to call a typed method wrapper with it.
+    ///
+    /// The calls made with the client directly are not counted by `Dispatch::LeastLoaded`.
+    pub fn client(&self) -> &APIClient {
+        &self.clients[self.pick()]
+    }
+
+    /// Calls an API method with the next client, given the method name and parameters.
+    pub async fn call_method<T: DeserializeOwned>(
+        &self,
+        method_name: &str,
+        params: Params,
+    ) -> Result<T> {
+        let index = self.pick();
+        let _in_progress = InProgress::new(&self.in_progress[index]);
+        self.clients[index].call_method(method_name, params).await
+    }
+
+    fn pick(&self) -> usize {
+        match self.dispatch {
+            Dispatch::RoundRobin => self.next.fetch_add(1, Ordering::Relaxed) % self.clients.len(),
+            Dispatch::LeastLoaded => self
+                .in_progress
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, n)| n.load(Ordering::SeqCst))
+                .map(|(index, _)| index)
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// Counts a call in progress until dropped (also when the call is cancelled).
+struct InProgress<'a>(&'a AtomicUsize);
+
+impl<'a> InProgress<'a> {
+    fn new(counter: &'a AtomicUsize) -> Self {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Self(counter)
+    }
+}
+
+impl Drop for InProgress<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockServer;
+    use serde_json::json;
+
+    async fn servers(n: i64) -> Vec<MockServer> {
+        let mut servers = Vec::new();
+        for i in 0..n {
+            servers.push(MockServer::json(json!({ "response": i })).await);
+        }
+        servers
+    }
+
+    fn pool(servers: &[MockServer]) -> PooledClient {
+        PooledClient::new(
+            servers
+                .iter()
+                .map(|server| {
+                    APIClient::builder("token")
+                        .base_url(server.base_url())
+                        .build()
+                })
+                .collect(),
+        )
+    }
+
+    #[tokio::test]
+    async fn round_robin() {
+        let servers = servers(3).await;
+        let pool = pool(&servers);
+        let mut answered = Vec::new();
+        for _ in 0..6 {
+            answered.push(
+                pool.call_method::<i64>("users.get", Params::new())
+                    .await
+                    .unwrap(),
+            );
+        }
+        assert_eq!(answered, vec![0, 1, 2, 0, 1, 2]);
+        assert!(servers.iter().all(|server| server.requests().len() == 2));
+    }
+
+    #[tokio::test]
+    async fn least_loaded() {
+        let servers = servers(2).await;
+        let pool = pool(&servers).dispatch(Dispatch::LeastLoaded);
+        pool.in_progress[0].fetch_add(1, Ordering::SeqCst);
+        let answered = pool
+            .call_method::<i64>("users.get", Params::new())
+            .await
+            .unwrap();
+        assert_eq!(answered, 1);
+        assert_eq!(pool.in_progress[1].load(Ordering::SeqCst), 0);
+    }
+}